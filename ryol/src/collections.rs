@@ -0,0 +1,417 @@
+use crate::std::logic::order_values;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
+use std::fmt;
+use std::rc::Rc;
+
+// unlike lists and maps these are shared between copies, so that pushing and
+// popping does not need the whole collection to be copied
+
+#[derive(Clone, Default)]
+pub struct Deque {
+    inner: Rc<RefCell<VecDeque<Value>>>,
+}
+
+impl Deque {
+    pub fn new(elements: Vec<Value>) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(elements.into())),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().is_empty()
+    }
+
+    pub fn push_front(&self, element: Value) {
+        self.inner.borrow_mut().push_front(element);
+    }
+
+    pub fn push_back(&self, element: Value) {
+        self.inner.borrow_mut().push_back(element);
+    }
+
+    pub fn pop_front(&self) -> Option<Value> {
+        self.inner.borrow_mut().pop_front()
+    }
+
+    pub fn pop_back(&self) -> Option<Value> {
+        self.inner.borrow_mut().pop_back()
+    }
+
+    pub fn front(&self) -> Option<Value> {
+        self.inner.borrow().front().cloned()
+    }
+
+    pub fn back(&self) -> Option<Value> {
+        self.inner.borrow().back().cloned()
+    }
+
+    // front to back
+    pub fn to_vec(&self) -> Vec<Value> {
+        self.inner.borrow().iter().cloned().collect()
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    // a copy that shares nothing with self, with each element passed through
+    // detach_value
+    pub fn detached(&self, detach_value: fn(Value) -> Value) -> Self {
+        Self::new(self.to_vec().into_iter().map(detach_value).collect())
+    }
+}
+
+struct HeapEntry {
+    priority: Value,
+    // elements with the same priority come out in the order they went in
+    sequence: u64,
+    value: Value,
+}
+
+// reversed as the std heap gives back the largest element first
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        order_values(&other.priority, &self.priority).then(other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapEntry {}
+
+#[derive(Default)]
+struct PriorityQueueInner {
+    heap: BinaryHeap<HeapEntry>,
+    next_sequence: u64,
+    // called on each element to get its priority, otherwise the element is
+    // its own priority
+    key_function: Option<Value>,
+}
+
+// the element with the smallest priority comes out first
+#[derive(Clone, Default)]
+pub struct PriorityQueue {
+    inner: Rc<RefCell<PriorityQueueInner>>,
+}
+
+impl PriorityQueue {
+    pub fn new(key_function: Option<Value>) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(PriorityQueueInner {
+                key_function,
+                ..Default::default()
+            })),
+        }
+    }
+
+    pub fn get_key_function(&self) -> Option<Value> {
+        self.inner.borrow().key_function.clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.borrow().heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().heap.is_empty()
+    }
+
+    pub fn push(&self, priority: Value, value: Value) {
+        let mut inner = self.inner.borrow_mut();
+
+        let sequence = inner.next_sequence;
+        inner.next_sequence += 1;
+        inner.heap.push(HeapEntry {
+            priority,
+            sequence,
+            value,
+        });
+    }
+
+    pub fn pop(&self) -> Option<Value> {
+        Some(self.inner.borrow_mut().heap.pop()?.value)
+    }
+
+    pub fn peek(&self) -> Option<Value> {
+        Some(self.inner.borrow().heap.peek()?.value.clone())
+    }
+
+    // in the order they would be popped
+    pub fn to_vec(&self) -> Vec<Value> {
+        let inner = self.inner.borrow();
+
+        let mut entries: Vec<&HeapEntry> = inner.heap.iter().collect();
+        entries.sort_by(|x, y| y.cmp(x));
+        entries
+            .into_iter()
+            .map(|entry| entry.value.clone())
+            .collect()
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    // priorities are already detached, so only the elements are passed
+    // through detach_value
+    pub fn detached(&self, detach_value: fn(Value) -> Value) -> Self {
+        let inner = self.inner.borrow();
+
+        let heap = inner
+            .heap
+            .iter()
+            .map(|entry| HeapEntry {
+                priority: entry.priority.clone(),
+                sequence: entry.sequence,
+                value: detach_value(entry.value.clone()),
+            })
+            .collect();
+
+        Self {
+            inner: Rc::new(RefCell::new(PriorityQueueInner {
+                heap,
+                next_sequence: inner.next_sequence,
+                key_function: inner.key_function.clone(),
+            })),
+        }
+    }
+}
+
+// keys are ordered with order_values, which compares numbers by their exact
+// value and structures by their template first. so keys like 1 and 1.0 are
+// the same key, and structures from different templates are not, as for MapKey
+#[derive(Clone)]
+struct SortedKey(Value);
+
+impl Ord for SortedKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        order_values(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for SortedKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SortedKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SortedKey {}
+
+#[derive(Clone, Default)]
+pub struct SortedMap {
+    inner: Rc<RefCell<BTreeMap<SortedKey, Value>>>,
+}
+
+// keys are checked by the caller, as with MapKey they can not change once added
+#[allow(clippy::mutable_key_type)]
+impl SortedMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().is_empty()
+    }
+
+    pub fn get(&self, key: &Value) -> Option<Value> {
+        self.inner.borrow().get(&SortedKey(key.clone())).cloned()
+    }
+
+    pub fn insert(&self, key: Value, value: Value) {
+        self.inner.borrow_mut().insert(SortedKey(key), value);
+    }
+
+    pub fn remove(&self, key: &Value) -> Option<Value> {
+        self.inner.borrow_mut().remove(&SortedKey(key.clone()))
+    }
+
+    pub fn first(&self) -> Option<(Value, Value)> {
+        let inner = self.inner.borrow();
+        let (key, value) = inner.first_key_value()?;
+        Some((key.0.clone(), value.clone()))
+    }
+
+    pub fn last(&self) -> Option<(Value, Value)> {
+        let inner = self.inner.borrow();
+        let (key, value) = inner.last_key_value()?;
+        Some((key.0.clone(), value.clone()))
+    }
+
+    pub fn pop_first(&self) -> Option<(Value, Value)> {
+        let (key, value) = self.inner.borrow_mut().pop_first()?;
+        Some((key.0, value))
+    }
+
+    pub fn pop_last(&self) -> Option<(Value, Value)> {
+        let (key, value) = self.inner.borrow_mut().pop_last()?;
+        Some((key.0, value))
+    }
+
+    // from start up to but not including end
+    pub fn range(&self, start: &Value, end: &Value) -> Vec<(Value, Value)> {
+        if order_values(start, end) != Ordering::Less {
+            return Vec::new();
+        }
+
+        self.inner
+            .borrow()
+            .range(SortedKey(start.clone())..SortedKey(end.clone()))
+            .map(|(key, value)| (key.0.clone(), value.clone()))
+            .collect()
+    }
+
+    // in key order
+    pub fn to_vec(&self) -> Vec<(Value, Value)> {
+        self.inner
+            .borrow()
+            .iter()
+            .map(|(key, value)| (key.0.clone(), value.clone()))
+            .collect()
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    // keys are already detached, so only the values are passed through
+    // detach_value
+    pub fn detached(&self, detach_value: fn(Value) -> Value) -> Self {
+        let inner = self
+            .inner
+            .borrow()
+            .iter()
+            .map(|(key, value)| (key.clone(), detach_value(value.clone())))
+            .collect();
+
+        Self {
+            inner: Rc::new(RefCell::new(inner)),
+        }
+    }
+}
+
+// true when value is the collection or holds it anywhere inside of it. used
+// to stop a collection being pushed into itself, which would make displaying
+// or comparing it recurse forever
+pub fn contains_collection(value: &Value, collection: &Value) -> bool {
+    match (value, collection) {
+        (Value::Deque(x), Value::Deque(y)) if x.ptr_eq(y) => true,
+        (Value::PriorityQueue(x), Value::PriorityQueue(y)) if x.ptr_eq(y) => true,
+        (Value::SortedMap(x), Value::SortedMap(y)) if x.ptr_eq(y) => true,
+        (Value::Structure(structure), _) => structure
+            .get_sorted_members()
+            .iter()
+            .any(|(_, member)| contains_collection(member, collection)),
+        // sets and map keys are hashable so can not hold any of these
+        (
+            Value::List(_)
+            | Value::Map(_)
+            | Value::Deque(_)
+            | Value::PriorityQueue(_)
+            | Value::SortedMap(_),
+            _,
+        ) => value
+            .iter()
+            .is_some_and(|mut elements| elements.any(|x| contains_collection(&x, collection))),
+        _ => false,
+    }
+}
+
+// these compare by their contents like the other collections
+
+impl PartialEq for Deque {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.to_vec() == other.to_vec()
+    }
+}
+
+impl PartialEq for PriorityQueue {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.to_vec() == other.to_vec()
+    }
+}
+
+impl PartialEq for SortedMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.to_vec() == other.to_vec()
+    }
+}
+
+fn write_elements(f: &mut fmt::Formatter, name: &str, elements: &[Value]) -> fmt::Result {
+    write!(f, "({}", name)?;
+
+    for element in elements {
+        write!(f, " {}", element)?;
+    }
+
+    write!(f, ")")
+}
+
+impl fmt::Display for Deque {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_elements(f, "make-deque", &self.to_vec())
+    }
+}
+
+impl fmt::Display for PriorityQueue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_elements(f, "make-heap", &self.to_vec())
+    }
+}
+
+impl fmt::Display for SortedMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries: Vec<Value> = self
+            .to_vec()
+            .into_iter()
+            .flat_map(|(key, value)| [key, value])
+            .collect();
+
+        write_elements(f, "make-sorted-map", &entries)
+    }
+}
+
+impl fmt::Debug for Deque {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.to_vec()).finish()
+    }
+}
+
+impl fmt::Debug for PriorityQueue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.to_vec()).finish()
+    }
+}
+
+impl fmt::Debug for SortedMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.to_vec()).finish()
+    }
+}
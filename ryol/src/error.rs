@@ -1,20 +1,58 @@
 use crate::tokeniser::Token;
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorKind {
+    Runtime,
+    Thrown,
+    Recursion,
+}
+
+impl ErrorKind {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            ErrorKind::Runtime => "runtime",
+            ErrorKind::Thrown => "thrown",
+            ErrorKind::Recursion => "recursion",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Error {
     message: String,
     token: Option<Token>,
+    kind: ErrorKind,
 }
 
 impl Error {
     pub fn new(message: String, token: Option<Token>) -> Self {
-        Self { message, token }
+        Self::new_with_kind(ErrorKind::Runtime, message, token)
+    }
+
+    pub fn new_with_kind(kind: ErrorKind, message: String, token: Option<Token>) -> Self {
+        Self {
+            message,
+            token,
+            kind,
+        }
+    }
+
+    pub fn get_message(&self) -> &String {
+        &self.message
+    }
+
+    pub fn get_token(&self) -> &Option<Token> {
+        &self.token
     }
 
     pub fn set_token(&mut self, token: Token) {
         self.token = Some(token)
     }
+
+    pub fn get_kind(&self) -> ErrorKind {
+        self.kind
+    }
 }
 
 impl fmt::Display for Error {
@@ -12,3 +12,25 @@ pub fn get_identifier(node: &Node) -> Result<&String, Error> {
         None => Err(Error::new("node must have a token".to_string(), None)),
     }
 }
+
+pub fn get_identifier_list(node: &Node) -> Result<Vec<String>, Error> {
+    let mut identifiers = Vec::with_capacity(node.get_children().len() + 1);
+
+    // "(a b c)" is parsed as "a" with the children "b" and "c"
+    if node.get_token().is_some() {
+        identifiers.push(get_identifier(node)?.clone());
+    }
+
+    for child in node.get_children() {
+        if child.is_bracketed() || !child.get_children().is_empty() {
+            return Err(Error::new(
+                "must be a list of identifiers".to_string(),
+                child.get_token().clone(),
+            ));
+        }
+
+        identifiers.push(get_identifier(child)?.clone());
+    }
+
+    Ok(identifiers)
+}
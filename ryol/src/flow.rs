@@ -0,0 +1,54 @@
+use crate::error::Error;
+use crate::function::Function;
+use crate::run_state::RunState;
+use crate::tokeniser::Token;
+use crate::value::Value;
+
+// what evaluating a node gives back, which is usually a value but can also be
+// control flow for something further out to handle. this is kept apart from
+// errors so that try and the error positions never have to look past it
+#[derive(Debug, Clone)]
+pub enum Flow {
+    Value(Value),
+    Break(Value),
+    Continue,
+    // a call in tail position, which the function it is in makes instead so
+    // that it runs in constant native stack space
+    TailCall(Function, Vec<Value>, Token),
+}
+
+impl Flow {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Flow::Value(_) => "value",
+            Flow::Break(_) => "break",
+            Flow::Continue => "continue",
+            Flow::TailCall(_, _, _) => "tail call",
+        }
+    }
+
+    pub fn is_value(&self) -> bool {
+        matches!(self, Flow::Value(_))
+    }
+
+    // for when the flow reaches something that needs a value, so break and
+    // continue have gone past every loop
+    pub fn into_value(self, run_state: &mut RunState) -> Result<Value, Error> {
+        match self {
+            Flow::Value(value) => Ok(value),
+            Flow::TailCall(function, args, token) => match function.call(run_state, args) {
+                Ok(value) => Ok(value),
+                Err(mut error) => {
+                    if error.get_token().is_none() {
+                        error.set_token(token);
+                    }
+                    Err(error)
+                }
+            },
+            Flow::Break(_) | Flow::Continue => Err(Error::new(
+                format!("\"{}\" used outside of a loop", self.get_name()),
+                None,
+            )),
+        }
+    }
+}
@@ -1,7 +1,185 @@
 use crate::error::Error;
+use crate::flow::Flow;
 use crate::node::Node;
-use crate::run_state::RunState;
+use crate::run_state::{RunState, Scope};
 use crate::value::Value;
+use std::fmt;
+use std::rc::Rc;
 
 pub type NativeFunction = fn(Vec<Value>) -> Result<Value, Error>;
-pub type NativeMacro = fn(&mut RunState, &Node) -> Result<Value, Error>;
+// for native functions that need the run state, such as to call functions they
+// are given, while still being values that can be passed around
+pub type NativeRunStateFunction = fn(&mut RunState, Vec<Value>) -> Result<Value, Error>;
+pub type NativeMacro = fn(&mut RunState, &Node) -> Result<Flow, Error>;
+
+struct FunctionInstance {
+    parameters: Vec<String>,
+    // "(a b & rest)" puts any extra arguments into a list called rest
+    rest_parameter: Option<String>,
+    body: Node,
+    // the non global scopes that were visible when the function was created
+    scope: Scope,
+}
+
+#[derive(Clone)]
+pub struct Function {
+    inner: Rc<FunctionInstance>,
+}
+
+impl Function {
+    pub fn new(mut parameters: Vec<String>, body: Node, scope: Scope) -> Result<Self, Error> {
+        let rest_parameter = match parameters.iter().position(|parameter| parameter == "&") {
+            Some(i) if i + 2 == parameters.len() => {
+                let rest_parameter = parameters.pop();
+                parameters.pop();
+                rest_parameter
+            }
+            Some(_) => {
+                return Err(Error::new(
+                    "\"&\" must be followed by exactly one parameter".to_string(),
+                    None,
+                ));
+            }
+            None => None,
+        };
+
+        let identifiers: Vec<&String> = parameters.iter().chain(&rest_parameter).collect();
+        for (i, parameter) in identifiers.iter().enumerate() {
+            if identifiers[..i].contains(parameter) {
+                return Err(Error::new(
+                    format!("parameter: \"{}\" is defined more than once", parameter),
+                    None,
+                ));
+            }
+        }
+
+        Ok(Self {
+            inner: Rc::new(FunctionInstance {
+                parameters,
+                rest_parameter,
+                body,
+                scope,
+            }),
+        })
+    }
+
+    pub fn get_parameters(&self) -> &Vec<String> {
+        &self.inner.parameters
+    }
+
+    pub fn get_rest_parameter(&self) -> &Option<String> {
+        &self.inner.rest_parameter
+    }
+
+    pub fn get_body(&self) -> &Node {
+        &self.inner.body
+    }
+
+    pub fn call(&self, run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+        run_state.enter_depth()?;
+        let res = self.call_in_depth(run_state, args);
+        run_state.exit_depth();
+
+        res
+    }
+
+    fn call_in_depth(&self, run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+        let mut function = self.clone();
+        let mut args = args;
+        let mut tail_call_token = None;
+
+        // calls in tail position come back here rather than recursing so
+        // that they run in constant native stack space
+        loop {
+            match function.call_once(run_state, args) {
+                Ok(Flow::TailCall(next_function, next_args, token)) => {
+                    function = next_function;
+                    args = next_args;
+                    tail_call_token = Some(token);
+                }
+                res => {
+                    // loops can not be broken out of from inside of a function
+                    // so break and continue become errors here
+                    let mut res = res.and_then(|flow| flow.into_value(run_state));
+
+                    if let (Err(error), Some(token)) = (&mut res, &tail_call_token) {
+                        if error.get_token().is_none() {
+                            error.set_token(token.clone());
+                        }
+                    }
+
+                    return res;
+                }
+            }
+        }
+    }
+
+    fn call_once(&self, run_state: &mut RunState, mut args: Vec<Value>) -> Result<Flow, Error> {
+        let parameters = self.get_parameters();
+
+        let arity_matches = match self.get_rest_parameter() {
+            Some(_) => args.len() >= parameters.len(),
+            None => args.len() == parameters.len(),
+        };
+
+        if !arity_matches {
+            return Err(Error::new(
+                format!(
+                    "function takes {}{} arguments, recieved: {}",
+                    if self.get_rest_parameter().is_some() {
+                        "at least "
+                    } else {
+                        ""
+                    },
+                    parameters.len(),
+                    args.len()
+                ),
+                None,
+            ));
+        }
+
+        let mut call_scope = Scope::new();
+
+        if let Some(rest_parameter) = self.get_rest_parameter() {
+            let rest = args.split_off(parameters.len());
+            call_scope.define_local(rest_parameter, Value::List(rest));
+        }
+
+        for (parameter, arg) in parameters.iter().zip(args) {
+            call_scope.define_local(parameter, arg);
+        }
+
+        let outer_scopes = run_state.enter_function_scope(self.inner.scope.clone(), call_scope);
+        let res = self.get_body().evaluate_flow(run_state);
+        run_state.exit_function_scope(outer_scopes);
+
+        res
+    }
+}
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+pub fn format_parameters(function: &Function) -> String {
+    let mut output = function.get_parameters().join(" ");
+
+    if let Some(rest_parameter) = function.get_rest_parameter() {
+        if !output.is_empty() {
+            output.push(' ');
+        }
+        output.push_str("& ");
+        output.push_str(rest_parameter);
+    }
+
+    output
+}
+
+// can not derive as the captured scope may contain the function itself
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Function({})", format_parameters(self))
+    }
+}
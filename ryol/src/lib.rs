@@ -1,11 +1,18 @@
+pub mod collections;
 pub mod error;
 pub mod expose_utils;
+pub mod flow;
 pub mod function;
+pub mod map;
 pub mod node;
 pub mod parser;
+pub mod random;
+pub mod range;
 pub mod run_state;
+pub mod set;
 pub mod std;
 pub mod structure;
+pub mod symbol;
 pub mod tokeniser;
 pub mod value;
 pub mod variable;
@@ -39,14 +46,22 @@ pub fn eval(source: &str) -> Result<Value, EvalError> {
 }
 
 pub mod prelude {
-    pub use crate::error::Error;
+    pub use crate::collections::{Deque, PriorityQueue, SortedMap};
+    pub use crate::error::{Error, ErrorKind};
     pub use crate::eval;
     pub use crate::expose_utils::*;
-    pub use crate::function::{NativeFunction, NativeMacro};
+    pub use crate::flow::Flow;
+    pub use crate::function::{Function, NativeFunction, NativeMacro, NativeRunStateFunction};
+    pub use crate::map::Map;
     pub use crate::node::Node;
+    pub use crate::random::Random;
+    pub use crate::range::Range;
     pub use crate::run_state::RunState;
+    pub use crate::set::Set;
     pub use crate::structure::{StructureInstance, StructureTemplate};
+    pub use crate::symbol::Symbol;
     pub use crate::tokeniser::{Token, TokenType};
     pub use crate::value::Value;
     pub use crate::variable::Variable;
+    pub use num_bigint::BigInt;
 }
@@ -0,0 +1,246 @@
+use crate::error::Error;
+use crate::std::maths::from_rational;
+use crate::value::Value;
+use num_rational::BigRational;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+// a value that is known to be usable as a key, which is any value that is
+// always equal to itself and does not change how it compares when copied
+#[derive(Clone)]
+pub struct MapKey {
+    value: Value,
+    // compared and hashed instead of value, so that numbers with the same
+    // exact value like 1 and 1.0 are the same key. structures are only the
+    // same key when their templates are too, so this agrees with "=" and with
+    // the order the sorted map uses
+    normalised: Value,
+}
+
+impl MapKey {
+    pub fn new(value: Value) -> Result<Self, Error> {
+        if !value.is_hashable() {
+            return Err(Error::new(
+                format!("can not use: {:?} as a key", value),
+                None,
+            ));
+        }
+
+        let value = detach(value);
+        Ok(Self {
+            normalised: normalise(value.clone()),
+            value,
+        })
+    }
+
+    pub fn get_value(&self) -> &Value {
+        &self.value
+    }
+
+    pub fn get_normalised(&self) -> &Value {
+        &self.normalised
+    }
+}
+
+// structures and the shared collections are shared between copies, so keys
+// take their own copy that nothing else can change. also used for structure
+// member defaults, so that each instance gets its own collections
+pub fn detach(value: Value) -> Value {
+    match value {
+        Value::List(list) => Value::List(list.into_iter().map(detach).collect()),
+        Value::Map(map) => {
+            let mut output = Map::new();
+            for key in map.keys.iter() {
+                // the key was already detached when it was added
+                output
+                    .values
+                    .insert(key.clone(), detach(map.values[key].clone()));
+                output.keys.push(key.clone());
+            }
+            Value::Map(output)
+        }
+        Value::Structure(structure) => Value::Structure(structure.detached(detach)),
+        Value::Deque(deque) => Value::Deque(deque.detached(detach)),
+        Value::PriorityQueue(queue) => Value::PriorityQueue(queue.detached(detach)),
+        Value::SortedMap(map) => Value::SortedMap(map.detached(detach)),
+        _ => value,
+    }
+}
+
+// finite floats become the exact integer or rational they are equal to,
+// everything else is already in its only form
+fn normalise(value: Value) -> Value {
+    match value {
+        Value::Float(float) if float.is_finite() => match BigRational::from_float(float) {
+            Some(rational) => from_rational(rational),
+            None => Value::Float(float),
+        },
+        Value::List(list) => Value::List(list.into_iter().map(normalise).collect()),
+        // map keys and set elements are already normalised
+        Value::Map(map) => {
+            let mut output = Map::new();
+            for key in map.keys.iter() {
+                output
+                    .values
+                    .insert(key.clone(), normalise(map.values[key].clone()));
+                output.keys.push(key.clone());
+            }
+            Value::Map(output)
+        }
+        Value::Structure(structure) => Value::Structure(structure.detached(normalise)),
+        _ => value,
+    }
+}
+
+// every value allowed by MapKey::new is equal to itself
+impl Eq for MapKey {}
+
+impl PartialEq for MapKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalised == other.normalised
+    }
+}
+
+impl Hash for MapKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalised.hash(state);
+    }
+}
+
+// combines hashes so that the order they are given in does not matter, for
+// collections where the order is not part of equality
+pub fn hash_unordered<'a, H: Hasher>(
+    values: impl Iterator<Item = (&'a Value, Option<&'a Value>)>,
+    state: &mut H,
+) {
+    let mut sum: u64 = 0;
+    let mut count: usize = 0;
+
+    for (key, value) in values {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        value.hash(&mut hasher);
+
+        sum = sum.wrapping_add(hasher.finish());
+        count += 1;
+    }
+
+    (count, sum).hash(state);
+}
+
+#[derive(Clone, Default)]
+pub struct Map {
+    // the order keys were first added in, so that output is always the same
+    keys: Vec<MapKey>,
+    values: HashMap<MapKey, Value>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.values.get(&MapKey::new(key.clone()).ok()?)
+    }
+
+    pub fn contains_key(&self, key: &Value) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn insert(&mut self, key: Value, value: Value) -> Result<(), Error> {
+        let key = MapKey::new(key)?;
+
+        if !self.values.contains_key(&key) {
+            self.keys.push(key.clone());
+        }
+        self.values.insert(key, value);
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &Value) -> Option<Value> {
+        let key = MapKey::new(key.clone()).ok()?;
+        let value = self.values.remove(&key)?;
+        self.keys.retain(|other| *other != key);
+
+        Some(value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Value, &Value)> {
+        self.keys
+            .iter()
+            .map(|key| (key.get_value(), &self.values[key]))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &Value> {
+        self.keys.iter().map(MapKey::get_value)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.keys.iter().map(|key| &self.values[key])
+    }
+}
+
+impl Hash for Map {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(
+            self.keys
+                .iter()
+                .map(|key| (key.get_normalised(), Some(&self.values[key]))),
+            state,
+        );
+    }
+}
+
+// the order that keys were added in does not matter
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .values
+                .iter()
+                .all(|(key, value)| other.values.get(key) == Some(value))
+    }
+}
+
+impl fmt::Debug for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (i, (key, value)) in self.iter().enumerate() {
+            write!(f, "{:?}: {:?}", key, value)?;
+
+            if i + 1 < self.len() {
+                write!(f, ", ")?;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (i, (key, value)) in self.iter().enumerate() {
+            write!(f, "{} {}", key, value)?;
+
+            if i + 1 < self.len() {
+                write!(f, " ")?;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
@@ -1,14 +1,26 @@
 use crate::error::Error;
-use crate::function::NativeFunction;
+use crate::flow::Flow;
+use crate::function::{Function, NativeFunction, NativeRunStateFunction};
 use crate::run_state::RunState;
-use crate::structure::StructureInstance;
+use crate::structure::{StructureInstance, StructureTemplate};
+use crate::symbol::Symbol;
 use crate::tokeniser::{Token, TokenType};
 use crate::value::Value;
 
-#[derive(Debug)]
+// when less native stack than this is left, evaluation carries on in a new
+// segment of stack on the heap so that deep recursion does not overflow it
+const STACK_RED_ZONE: usize = 128 * 1024;
+const STACK_SEGMENT_SIZE: usize = 2 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
 pub struct Node {
     token: Option<Token>,
     children: Vec<Node>,
+    // whether the token was the first thing inside a pair of brackets, as
+    // "(f)" should call f whereas "f" on its own is just the value of f
+    bracketed: bool,
+    // whether this is the last thing evaluated in a function body
+    tail: bool,
 }
 
 impl Node {
@@ -16,9 +28,19 @@ impl Node {
         Node {
             token,
             children: Vec::new(),
+            bracketed: false,
+            tail: false,
         }
     }
 
+    pub fn set_bracketed(&mut self, bracketed: bool) {
+        self.bracketed = bracketed;
+    }
+
+    pub fn is_bracketed(&self) -> bool {
+        self.bracketed
+    }
+
     pub fn add_child(&mut self, child: Node) {
         self.children.push(child);
     }
@@ -31,19 +53,366 @@ impl Node {
         &self.children
     }
 
+    // marks the calls that are the last thing evaluated so that functions can
+    // loop on them instead of recursing, this only looks through the built in
+    // forms where the value of a child is the value of the whole form
+    pub fn mark_tail_position(&mut self) {
+        let identifier = match self.token.as_ref().map(Token::get_token_type) {
+            None => {
+                if let Some(last) = self.children.last_mut() {
+                    last.mark_tail_position();
+                }
+                return;
+            }
+            Some(TokenType::Identifier(identifier))
+                if self.bracketed || !self.children.is_empty() =>
+            {
+                identifier.as_str()
+            }
+            _ => return,
+        };
+
+        match identifier {
+            "if" => self.mark_if_tail_positions(),
+            "match" => {
+                for clause in self.children.iter_mut().skip(1) {
+                    clause.mark_match_clause_tail_position();
+                }
+            }
+            "do" => {
+                if let Some(last) = self.children.last_mut() {
+                    last.mark_tail_position();
+                }
+            }
+            "let" => {
+                if self.children.len() > 1 {
+                    self.children.last_mut().unwrap().mark_tail_position();
+                }
+            }
+            _ => self.tail = true,
+        }
+    }
+
+    fn mark_if_tail_positions(&mut self) {
+        // follows the same steps as std_basic_if to find the branch bodies
+        enum IfMode {
+            Normal,
+            Condition,
+            Body,
+        }
+
+        let mut if_mode = IfMode::Condition;
+        for child in self.children.iter_mut() {
+            if_mode = match if_mode {
+                IfMode::Condition => IfMode::Body,
+                IfMode::Body => {
+                    child.mark_tail_position();
+                    IfMode::Normal
+                }
+                IfMode::Normal => {
+                    let keyword = match child.token.as_ref().map(Token::get_token_type) {
+                        Some(TokenType::Identifier(identifier))
+                            if !child.bracketed && child.children.is_empty() =>
+                        {
+                            identifier.as_str()
+                        }
+                        _ => "",
+                    };
+
+                    match keyword {
+                        "elseif" | "elif" => IfMode::Condition,
+                        "else" => IfMode::Body,
+                        _ => {
+                            child.mark_tail_position();
+                            IfMode::Normal
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn mark_match_clause_tail_position(&mut self) {
+        // follows the same steps as std_pattern_match to find the clause body
+        let pattern_count = if self.token.is_some() { 0 } else { 1 };
+        let rest = self.children.get(pattern_count..).unwrap_or_default();
+
+        let has_guard = rest.first().is_some_and(|keyword| {
+            !keyword.bracketed
+                && keyword.children.is_empty()
+                && matches!(
+                    keyword.token.as_ref().map(Token::get_token_type),
+                    Some(TokenType::Identifier(identifier)) if identifier == "when"
+                )
+        });
+        let guard_count = if has_guard { 2 } else { 0 };
+
+        if rest.len() > guard_count {
+            self.children.last_mut().unwrap().mark_tail_position();
+        }
+    }
+
+    // "(a b c)" is parsed as "a" with the children "b" and "c" so this gives
+    // back "a" as a node of its own
+    pub fn get_head(&self) -> Option<Node> {
+        self.token
+            .as_ref()
+            .map(|token| Node::new(Some(token.clone())))
+    }
+
+    // turns the node back into data, with identifiers becoming symbols and
+    // bracketed expressions becoming lists
+    pub fn to_value(&self) -> Value {
+        let atom = self
+            .token
+            .as_ref()
+            .map(|token| match token.get_token_type() {
+                TokenType::Identifier(identifier) => Value::Symbol(Symbol::new(identifier)),
+                TokenType::Keyword(keyword) => Value::Keyword(Symbol::new(keyword)),
+                TokenType::Integer(integer) => Value::Integer(*integer),
+                TokenType::BigInteger(big_integer) => Value::BigInteger(big_integer.clone()),
+                TokenType::Rational(rational) => Value::Rational(rational.clone()),
+                TokenType::Float(float) => Value::Float(*float),
+                TokenType::String(string) => Value::String(string.clone()),
+                TokenType::LBracket
+                | TokenType::RBracket
+                | TokenType::LBrace
+                | TokenType::RBrace => {
+                    unreachable!()
+                }
+            });
+
+        match atom {
+            Some(atom) if !self.bracketed && self.children.is_empty() => atom,
+            _ => {
+                let mut list = Vec::with_capacity(self.children.len() + 1);
+                list.extend(atom);
+                list.extend(self.children.iter().map(Node::to_value));
+
+                Value::List(list)
+            }
+        }
+    }
+
+    // the opposite of to_value, the token is used for the position of the new nodes
+    pub fn from_value(value: &Value, token: &Token) -> Result<Node, Error> {
+        let new_token = |token_type| {
+            Some(Token::new(
+                token_type,
+                token.get_line_no(),
+                token.get_col_no(),
+            ))
+        };
+
+        match value {
+            Value::Null => Ok(Node::new(None)),
+            Value::Boolean(boolean) => Ok(Node::new(new_token(TokenType::Identifier(
+                boolean.to_string(),
+            )))),
+            Value::Integer(integer) => Ok(Node::new(new_token(TokenType::Integer(*integer)))),
+            Value::BigInteger(big_integer) => Ok(Node::new(new_token(TokenType::BigInteger(
+                big_integer.clone(),
+            )))),
+            Value::Rational(rational) => {
+                Ok(Node::new(new_token(TokenType::Rational(rational.clone()))))
+            }
+            Value::Float(float) => Ok(Node::new(new_token(TokenType::Float(*float)))),
+            Value::String(string) => Ok(Node::new(new_token(TokenType::String(string.clone())))),
+            Value::Symbol(symbol) => Ok(Node::new(new_token(TokenType::Identifier(
+                symbol.to_string(),
+            )))),
+            Value::Keyword(keyword) => Ok(Node::new(new_token(TokenType::Keyword(
+                keyword.to_string(),
+            )))),
+            Value::List(list) => {
+                let (mut node, rest) = match list.split_first() {
+                    Some((Value::Symbol(symbol), rest)) => {
+                        let mut node =
+                            Node::new(new_token(TokenType::Identifier(symbol.to_string())));
+                        node.set_bracketed(true);
+                        (node, rest)
+                    }
+                    _ => (Node::new(None), list.as_slice()),
+                };
+
+                for element in rest {
+                    node.add_child(Node::from_value(element, token)?);
+                }
+
+                Ok(node)
+            }
+            Value::Map(map) => {
+                let mut node = Node::new(new_token(TokenType::Identifier("make-map".to_string())));
+                node.set_bracketed(true);
+
+                for (key, value) in map.iter() {
+                    node.add_child(Node::from_value(key, token)?);
+                    node.add_child(Node::from_value(value, token)?);
+                }
+
+                Ok(node)
+            }
+            Value::Set(set) => {
+                let mut node = Node::new(new_token(TokenType::Identifier("make-set".to_string())));
+                node.set_bracketed(true);
+
+                for element in set.iter() {
+                    node.add_child(Node::from_value(element, token)?);
+                }
+
+                Ok(node)
+            }
+            _ => Err(Error::new(
+                format!("can not turn: {:?} into code", value),
+                Some(token.clone()),
+            )),
+        }
+    }
+
+    fn evaluate_arguments(&self, run_state: &mut RunState) -> Result<Vec<Value>, Error> {
+        let mut args = Vec::with_capacity(self.children.len());
+        for child in &self.children {
+            args.push(child.evaluate(run_state)?);
+        }
+
+        Ok(args)
+    }
+
     pub fn evaluate_native_function(
         &self,
         run_state: &mut RunState,
         native_function: NativeFunction,
         token: &Token,
     ) -> Result<Value, Error> {
+        let args = self.evaluate_arguments(run_state)?;
+
+        match native_function(args) {
+            Ok(res) => Ok(res),
+            Err(mut error) => {
+                error.set_token(token.clone());
+                Err(error)
+            }
+        }
+    }
+
+    pub fn evaluate_native_run_state_function(
+        &self,
+        run_state: &mut RunState,
+        native_function: NativeRunStateFunction,
+        token: &Token,
+    ) -> Result<Value, Error> {
+        let args = self.evaluate_arguments(run_state)?;
+
+        match native_function(run_state, args) {
+            Ok(res) => Ok(res),
+            Err(mut error) => {
+                // keep the position of errors from functions it called
+                if error.get_token().is_none() {
+                    error.set_token(token.clone());
+                }
+                Err(error)
+            }
+        }
+    }
+
+    pub fn evaluate_function(
+        &self,
+        run_state: &mut RunState,
+        function: &Function,
+        token: &Token,
+    ) -> Result<Flow, Error> {
         let mut args = Vec::with_capacity(self.children.len());
         for child in &self.children {
             args.push(child.evaluate(run_state)?);
         }
 
-        match native_function(args) {
-            Ok(res) => Ok(res),
+        // let the function this is in loop on the call instead
+        if self.tail {
+            return Ok(Flow::TailCall(function.clone(), args, token.clone()));
+        }
+
+        match function.call(run_state, args) {
+            Ok(res) => Ok(Flow::Value(res)),
+            Err(mut error) => {
+                // keep the position of errors from inside of the function body
+                if error.get_token().is_none() {
+                    error.set_token(token.clone());
+                }
+                Err(error)
+            }
+        }
+    }
+
+    pub fn expand_macro(
+        &self,
+        run_state: &mut RunState,
+        function: &Function,
+        token: &Token,
+    ) -> Result<Node, Error> {
+        // the arguments are passed as code rather than being evaluated
+        let args = self.children.iter().map(Node::to_value).collect();
+
+        match function.call(run_state, args) {
+            Ok(expansion) => Node::from_value(&expansion, token),
+            Err(mut error) => {
+                if error.get_token().is_none() {
+                    error.set_token(token.clone());
+                }
+                Err(error)
+            }
+        }
+    }
+
+    pub fn evaluate_macro(
+        &self,
+        run_state: &mut RunState,
+        function: &Function,
+        token: &Token,
+    ) -> Result<Flow, Error> {
+        let mut expansion = self.expand_macro(run_state, function, token)?;
+
+        if self.tail {
+            expansion.mark_tail_position();
+        }
+
+        expansion.evaluate_flow(run_state)
+    }
+
+    // "(name member value ...)", where the member names are not evaluated
+    pub fn evaluate_structure_construction(
+        &self,
+        run_state: &mut RunState,
+        structure_template: &StructureTemplate,
+        token: &Token,
+    ) -> Result<Value, Error> {
+        if !self.children.len().is_multiple_of(2) {
+            return Err(Error::new(
+                "structure construction requires pairs of member names and values".to_string(),
+                Some(token.clone()),
+            ));
+        }
+
+        let mut values = Vec::with_capacity(self.children.len() / 2);
+        for pair in self.children.chunks(2) {
+            let identifier = match pair[0].token.as_ref().map(Token::get_token_type) {
+                Some(TokenType::Identifier(identifier))
+                    if !pair[0].bracketed && pair[0].children.is_empty() =>
+                {
+                    identifier.clone()
+                }
+                _ => {
+                    return Err(Error::new(
+                        "member name must be an identifier".to_string(),
+                        Some(token.clone()),
+                    ));
+                }
+            };
+
+            values.push((identifier, pair[1].evaluate(run_state)?));
+        }
+
+        match StructureInstance::from_template_with_values(structure_template, values) {
+            Ok(structure) => Ok(Value::Structure(structure)),
             Err(mut error) => {
                 error.set_token(token.clone());
                 Err(error)
@@ -56,17 +425,31 @@ impl Node {
         run_state: &mut RunState,
         token: &Token,
         identifier: &String,
-    ) -> Result<Value, Error> {
+    ) -> Result<Flow, Error> {
         if let Some(structure_template) = run_state.find_structure_template(identifier) {
-            Ok(Value::Structure(StructureInstance::from_template(
-                structure_template,
-            )))
+            // "(P)" is the constructor that existed before named members, and
+            // members are set one at a time after it. so it never checks for
+            // missing members, those without a default start as null
+            if self.children.is_empty() {
+                Ok(Flow::Value(Value::Structure(
+                    StructureInstance::from_template(structure_template),
+                )))
+            } else {
+                let structure_template = structure_template.clone();
+                self.evaluate_structure_construction(run_state, &structure_template, token)
+                    .map(Flow::Value)
+            }
         } else if let Some(local) = run_state.find_local(identifier) {
             match local.clone() {
-                Value::NativeFunction(func) => {
-                    self.evaluate_native_function(run_state, func, token)
-                }
+                Value::NativeFunction(func) => self
+                    .evaluate_native_function(run_state, func, token)
+                    .map(Flow::Value),
+                Value::NativeRunStateFunction(func) => self
+                    .evaluate_native_run_state_function(run_state, func, token)
+                    .map(Flow::Value),
                 Value::NativeMacro(func) => func(run_state, self),
+                Value::Function(function) => self.evaluate_function(run_state, &function, token),
+                Value::Macro(function) => self.evaluate_macro(run_state, &function, token),
                 _ => Err(Error::new(
                     "must be a function or macro".to_string(),
                     self.token.clone(),
@@ -84,7 +467,7 @@ impl Node {
         &self,
         run_state: &mut RunState,
         token: &Token,
-    ) -> Result<Value, Error> {
+    ) -> Result<Flow, Error> {
         match token.get_token_type() {
             TokenType::Identifier(identifier) => {
                 self.evaluate_branch_identifier(run_state, token, identifier)
@@ -101,18 +484,27 @@ impl Node {
         run_state: &mut RunState,
         token: &Token,
         identifier: &String,
-    ) -> Result<Value, Error> {
+    ) -> Result<Flow, Error> {
         if let Some(structure_template) = run_state.find_structure_template(identifier) {
-            Ok(Value::Structure(StructureInstance::from_template(
-                structure_template,
+            Ok(Flow::Value(Value::Structure(
+                StructureInstance::from_template(structure_template),
             )))
         } else if let Some(local) = run_state.find_local(identifier) {
             match local {
-                Value::NativeFunction(func) => {
-                    self.evaluate_native_function(run_state, func, token)
+                Value::NativeFunction(func) if self.bracketed => self
+                    .evaluate_native_function(run_state, func, token)
+                    .map(Flow::Value),
+                Value::NativeRunStateFunction(func) if self.bracketed => self
+                    .evaluate_native_run_state_function(run_state, func, token)
+                    .map(Flow::Value),
+                Value::Function(function) if self.bracketed => {
+                    self.evaluate_function(run_state, &function, token)
+                }
+                Value::Macro(function) if self.bracketed => {
+                    self.evaluate_macro(run_state, &function, token)
                 }
                 Value::NativeMacro(func) => func(run_state, self),
-                _ => Ok(local.clone()),
+                _ => Ok(Flow::Value(local.clone())),
             }
         } else {
             Err(Error::new(
@@ -126,41 +518,75 @@ impl Node {
         &self,
         run_state: &mut RunState,
         token: &Token,
-    ) -> Result<Value, Error> {
+    ) -> Result<Flow, Error> {
         // just this one node
-        match token.get_token_type() {
+        let value = match token.get_token_type() {
             TokenType::Identifier(identifier) => {
-                self.evaluate_leaf_identifer(run_state, token, identifier)
+                return self.evaluate_leaf_identifer(run_state, token, identifier);
             }
-            TokenType::Integer(integer) => Ok(Value::Integer(*integer)),
-            TokenType::Float(float) => Ok(Value::Float(*float)),
-            TokenType::String(string) => Ok(Value::String(string.clone())),
-            TokenType::LBracket | TokenType::RBracket => {
+            TokenType::Integer(integer) => Value::Integer(*integer),
+            TokenType::BigInteger(big_integer) => Value::BigInteger(big_integer.clone()),
+            TokenType::Rational(rational) => Value::Rational(rational.clone()),
+            TokenType::Float(float) => Value::Float(*float),
+            TokenType::String(string) => Value::String(string.clone()),
+            TokenType::Keyword(keyword) => Value::Keyword(Symbol::new(keyword)),
+            TokenType::LBracket | TokenType::RBracket | TokenType::LBrace | TokenType::RBrace => {
                 unreachable!()
             }
-        }
+        };
+
+        Ok(Flow::Value(value))
     }
 
+    // for everywhere that needs a value, which is as far as break and continue
+    // can go
     pub fn evaluate(&self, run_state: &mut RunState) -> Result<Value, Error> {
+        let flow = self.evaluate_flow(run_state)?;
+
+        match flow.into_value(run_state) {
+            Ok(value) => Ok(value),
+            Err(mut error) => {
+                if let (None, Some(token)) = (error.get_token(), &self.token) {
+                    error.set_token(token.clone());
+                }
+                Err(error)
+            }
+        }
+    }
+
+    // for the bodies of blocks, loops and functions, which pass on control flow
+    // rather than turning it into a value
+    pub fn evaluate_flow(&self, run_state: &mut RunState) -> Result<Flow, Error> {
+        stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || {
+            self.evaluate_node(run_state)
+        })
+    }
+
+    fn evaluate_node(&self, run_state: &mut RunState) -> Result<Flow, Error> {
         match (&self.token, !self.children.is_empty()) {
             (Some(token), true) => self.evaluate_token_with_children(run_state, token),
             (Some(token), false) => self.evaluate_leaf_node(run_state, token),
             (None, true) => {
                 if self.children.len() == 1 {
-                    self.children.first().unwrap().evaluate(run_state)
+                    self.children.first().unwrap().evaluate_flow(run_state)
                 } else {
-                    let mut last_value = Value::default();
+                    let mut last_flow = Flow::Value(Value::default());
 
                     for child in &self.children {
-                        last_value = child.evaluate(run_state)?;
+                        last_flow = child.evaluate_flow(run_state)?;
+
+                        // the rest is skipped by break and continue
+                        if !last_flow.is_value() {
+                            break;
+                        }
                     }
 
-                    Ok(last_value)
+                    Ok(last_flow)
                 }
             }
             (None, false) => {
                 // like the rust () for null
-                Ok(Value::Null)
+                Ok(Flow::Value(Value::Null))
             }
         }
     }
@@ -25,14 +25,20 @@ impl fmt::Display for ParserError {
     }
 }
 
+// how deeply brackets can be nested before giving up, as parsing (and
+// evaluating) goes deeper into the native stack for each level
+pub const MAX_NESTING_DEPTH: usize = 256;
+
 struct ParserState {
     tokens: VecDeque<Token>,
+    depth: usize,
 }
 
 impl ParserState {
     pub fn new(tokens: Vec<Token>) -> Self {
         Self {
             tokens: VecDeque::from(tokens),
+            depth: 0,
         }
     }
 
@@ -41,18 +47,99 @@ impl ParserState {
     }
 }
 
-fn parse_node(parser_state: &mut ParserState) -> Result<Node, ParserError> {
+fn parse_bracketed_node(
+    parser_state: &mut ParserState,
+    open_token: Token,
+) -> Result<Node, ParserError> {
+    if parser_state.depth >= MAX_NESTING_DEPTH {
+        return Err(ParserError {
+            message: format!(
+                "brackets can not be nested more than {} deep",
+                MAX_NESTING_DEPTH
+            ),
+            token: open_token,
+        });
+    }
+
+    parser_state.depth += 1;
+    let res = match open_token.get_token_type() {
+        TokenType::LBrace => parse_map_node(parser_state, open_token),
+        _ => parse_node(parser_state, true),
+    };
+    parser_state.depth -= 1;
+
+    res
+}
+
+// "{k v ...}" is turned into "(make-map k v ...)"
+fn parse_map_node(parser_state: &mut ParserState, open_token: Token) -> Result<Node, ParserError> {
+    let mut node = Node::new(Some(Token::new(
+        TokenType::Identifier("make-map".to_string()),
+        open_token.get_line_no(),
+        open_token.get_col_no(),
+    )));
+    node.set_bracketed(true);
+
+    loop {
+        match parser_state.eat_token() {
+            Some(token) => match token.get_token_type() {
+                TokenType::LBracket | TokenType::LBrace => {
+                    node.add_child(parse_bracketed_node(parser_state, token)?);
+                }
+                TokenType::RBrace => {
+                    return Ok(node);
+                }
+                TokenType::RBracket => {
+                    return Err(ParserError {
+                        message: "expected \"}\" before \")\"".to_string(),
+                        token,
+                    });
+                }
+                TokenType::Identifier(_)
+                | TokenType::Keyword(_)
+                | TokenType::Integer(_)
+                | TokenType::BigInteger(_)
+                | TokenType::Rational(_)
+                | TokenType::Float(_)
+                | TokenType::String(_) => node.add_child(Node::new(Some(token))),
+            },
+            None => {
+                return Err(ParserError {
+                    message: "\"{\" is never closed".to_string(),
+                    token: open_token,
+                });
+            }
+        }
+    }
+}
+
+fn parse_node(parser_state: &mut ParserState, bracketed: bool) -> Result<Node, ParserError> {
     // deal with node vertex
     let mut node = match parser_state.eat_token() {
         Some(node_token) => {
-            if *node_token.get_token_type() == TokenType::LBracket {
+            if matches!(
+                node_token.get_token_type(),
+                TokenType::LBracket | TokenType::LBrace
+            ) {
                 let mut node = Node::new(None);
-                node.add_child(parse_node(parser_state)?);
+                node.add_child(parse_bracketed_node(parser_state, node_token)?);
                 node
             } else if *node_token.get_token_type() == TokenType::RBracket {
+                if bracketed {
+                    // empty brackets so there is nothing left to this node
+                    return Ok(Node::new(None));
+                }
+
                 Node::new(None)
+            } else if *node_token.get_token_type() == TokenType::RBrace {
+                return Err(ParserError {
+                    message: "\"}\" does not close a \"{\"".to_string(),
+                    token: node_token,
+                });
             } else {
-                Node::new(Some(node_token))
+                let mut node = Node::new(Some(node_token));
+                node.set_bracketed(bracketed);
+                node
             }
         }
         None => Node::new(None),
@@ -62,14 +149,23 @@ fn parse_node(parser_state: &mut ParserState) -> Result<Node, ParserError> {
     loop {
         match parser_state.eat_token() {
             Some(token) => match token.get_token_type() {
-                TokenType::LBracket => {
-                    node.add_child(parse_node(parser_state)?);
+                TokenType::LBracket | TokenType::LBrace => {
+                    node.add_child(parse_bracketed_node(parser_state, token)?);
                 }
                 TokenType::RBracket => {
                     return Ok(node);
                 }
+                TokenType::RBrace => {
+                    return Err(ParserError {
+                        message: "\"}\" does not close a \"{\"".to_string(),
+                        token,
+                    });
+                }
                 TokenType::Identifier(_)
+                | TokenType::Keyword(_)
                 | TokenType::Integer(_)
+                | TokenType::BigInteger(_)
+                | TokenType::Rational(_)
                 | TokenType::Float(_)
                 | TokenType::String(_) => node.add_child(Node::new(Some(token))),
             },
@@ -82,5 +178,5 @@ fn parse_node(parser_state: &mut ParserState) -> Result<Node, ParserError> {
 
 pub fn parse(tokens: Vec<Token>) -> Result<Node, ParserError> {
     let mut parser_state = ParserState::new(tokens);
-    parse_node(&mut parser_state)
+    parse_node(&mut parser_state, false)
 }
@@ -0,0 +1,88 @@
+use crate::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Range {
+    start: i64,
+    end: i64,
+    step: i64,
+}
+
+impl Range {
+    pub fn new(start: i64, end: i64, step: i64) -> Result<Self, Error> {
+        if step == 0 {
+            return Err(Error::new("range step can not be zero".to_string(), None));
+        }
+
+        Ok(Self { start, end, step })
+    }
+
+    pub fn get_start(&self) -> i64 {
+        self.start
+    }
+
+    pub fn get_end(&self) -> i64 {
+        self.end
+    }
+
+    pub fn get_step(&self) -> i64 {
+        self.step
+    }
+
+    // worked out directly so that large ranges do not need to be stepped through
+    pub fn len(&self) -> usize {
+        let (start, end, step) = (self.start as i128, self.end as i128, self.step as i128);
+        let distance = if step > 0 { end - start } else { start - end };
+
+        if distance <= 0 {
+            0
+        } else {
+            ((distance + step.abs() - 1) / step.abs()) as usize
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> RangeIter {
+        RangeIter {
+            current: Some(self.start),
+            range: self.clone(),
+        }
+    }
+}
+
+pub struct RangeIter {
+    // none once the end has been reached or stepping past it would overflow
+    current: Option<i64>,
+    range: Range,
+}
+
+impl Iterator for RangeIter {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let current = self.current?;
+
+        let in_range = if self.range.step > 0 {
+            current < self.range.end
+        } else {
+            current > self.range.end
+        };
+
+        if !in_range {
+            self.current = None;
+            return None;
+        }
+
+        self.current = current.checked_add(self.range.step);
+        Some(current)
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(range {} {} {})", self.start, self.end, self.step)
+    }
+}
@@ -1,12 +1,14 @@
-use crate::error::Error;
-use crate::function::{NativeFunction, NativeMacro};
+use crate::error::{Error, ErrorKind};
+use crate::function::{NativeFunction, NativeMacro, NativeRunStateFunction};
 use crate::parser;
+use crate::random::Random;
 use crate::std::add_std_lib;
 use crate::structure::StructureTemplate;
 use crate::tokeniser;
 use crate::value::Value;
 use crate::variable::Variable;
 use crate::EvalError;
+use regex::Regex;
 use std::collections::{HashMap, VecDeque};
 
 #[derive(Debug, Clone)]
@@ -36,6 +38,17 @@ impl Scope {
         self.locals.get_mut(identifier)
     }
 
+    pub fn define_local(&mut self, identifier: &str, value: Value) {
+        self.locals
+            .insert(identifier.to_string(), Variable::new(value));
+    }
+
+    // can not be changed with set, but can still be shadowed by an inner scope
+    pub fn define_const(&mut self, identifier: &str, value: Value) {
+        self.locals
+            .insert(identifier.to_string(), Variable::new_const(value));
+    }
+
     pub fn set_const(&mut self, identifier: &String, value: Value) -> Result<(), Error> {
         if self.locals.get_mut(identifier).is_some() {
             Err(Error::new(
@@ -94,10 +107,24 @@ impl Default for Scope {
     }
 }
 
+// how many function calls can be inside of each other before giving up, calls
+// in tail position do not count. the native stack grows as needed so this only
+// stops runaway recursion from using up all of the memory
+pub const DEFAULT_MAX_DEPTH: usize = 10_000;
+
+// the cache is cleared when full so that generated patterns can not grow it
+// forever
+const REGEX_CACHE_SIZE: usize = 256;
+
 #[derive(Debug, Clone)]
 pub struct RunState {
     scopes: VecDeque<Scope>,
     use_structures: bool,
+    gensym_count: usize,
+    depth: usize,
+    max_depth: usize,
+    random: Random,
+    regex_cache: HashMap<String, Regex>,
 }
 
 impl RunState {
@@ -105,6 +132,11 @@ impl RunState {
         let mut output = Self {
             scopes: VecDeque::with_capacity(512),
             use_structures: include_std_lib,
+            gensym_count: 0,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            random: Random::from_entropy(),
+            regex_cache: HashMap::new(),
         };
 
         // make sure there is one global scope
@@ -130,6 +162,61 @@ impl RunState {
         self.use_structures
     }
 
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn enter_depth(&mut self) -> Result<(), Error> {
+        if self.depth >= self.max_depth {
+            return Err(Error::new_with_kind(
+                ErrorKind::Recursion,
+                "maximum recursion depth exceeded".to_string(),
+                None,
+            ));
+        }
+
+        self.depth += 1;
+        Ok(())
+    }
+
+    pub fn exit_depth(&mut self) {
+        self.depth -= 1;
+    }
+
+    // each run state has its own generator so that seeding one does not
+    // affect any others
+    pub fn seed_random(&mut self, seed: u64) {
+        self.random = Random::new(seed);
+    }
+
+    pub fn get_random_mut(&mut self) -> &mut Random {
+        &mut self.random
+    }
+
+    // compiles each pattern once, regexes are cheap to clone
+    pub fn get_regex(&mut self, pattern: &str) -> Result<Regex, regex::Error> {
+        if let Some(regex) = self.regex_cache.get(pattern) {
+            return Ok(regex.clone());
+        }
+
+        let regex = Regex::new(pattern)?;
+        if self.regex_cache.len() >= REGEX_CACHE_SIZE {
+            self.regex_cache.clear();
+        }
+        self.regex_cache.insert(pattern.to_string(), regex.clone());
+
+        Ok(regex)
+    }
+
+    pub fn next_gensym_id(&mut self) -> usize {
+        self.gensym_count += 1;
+        self.gensym_count
+    }
+
     pub fn find_local(&self, identifier: &String) -> Option<Value> {
         for scope in self.scopes.iter().rev() {
             if scope.local_exists(identifier) {
@@ -140,6 +227,18 @@ impl RunState {
         None
     }
 
+    pub fn set_local(&mut self, identifier: &String, value: Value) -> Result<(), Error> {
+        // update the nearest existing binding, otherwise define a new one in the
+        // innermost scope
+        for scope in self.scopes.iter_mut().rev() {
+            if scope.local_exists(identifier) {
+                return scope.set_local(identifier, value);
+            }
+        }
+
+        self.get_local_scope_mut().set_local(identifier, value)
+    }
+
     pub fn find_structure_template(&self, identifier: &String) -> Option<&StructureTemplate> {
         for scope in self.scopes.iter().rev() {
             if scope.structure_template_exists(identifier) {
@@ -158,15 +257,77 @@ impl RunState {
         self.scopes.back_mut().unwrap()
     }
 
+    pub fn push_scope(&mut self) {
+        self.scopes.push_back(Scope::new());
+    }
+
+    pub fn pop_scope(&mut self) {
+        // the global scope should never be removed
+        if self.scopes.len() > 1 {
+            self.scopes.pop_back();
+        }
+    }
+
+    pub fn capture_scope(&self) -> Scope {
+        let mut captured = Scope::new();
+
+        // the global scope is always visible so does not need capturing, and the
+        // variables are shared so that closures see (and make) later changes
+        for scope in self.scopes.iter().skip(1) {
+            for (identifier, variable) in &scope.locals {
+                captured.locals.insert(identifier.clone(), variable.clone());
+            }
+
+            for (identifier, structure_template) in &scope.structure_templates {
+                captured
+                    .structure_templates
+                    .insert(identifier.clone(), structure_template.clone());
+            }
+        }
+
+        captured
+    }
+
+    pub fn enter_function_scope(
+        &mut self,
+        captured_scope: Scope,
+        call_scope: Scope,
+    ) -> VecDeque<Scope> {
+        let outer_scopes = self.scopes.split_off(1);
+
+        self.scopes.push_back(captured_scope);
+        self.scopes.push_back(call_scope);
+
+        outer_scopes
+    }
+
+    pub fn exit_function_scope(&mut self, outer_scopes: VecDeque<Scope>) {
+        self.scopes.truncate(1);
+        self.scopes.extend(outer_scopes);
+    }
+
     pub fn expose(&mut self, name: &str, value: Value) -> Result<(), Error> {
         self.get_global_scope_mut()
             .set_local(&name.to_string(), value)
     }
 
+    pub fn expose_const(&mut self, name: &str, value: Value) -> Result<(), Error> {
+        self.get_global_scope_mut().define_const(name, value);
+        Ok(())
+    }
+
     pub fn expose_function(&mut self, name: &str, function: NativeFunction) -> Result<(), Error> {
         self.expose(name, Value::NativeFunction(function))
     }
 
+    pub fn expose_run_state_function(
+        &mut self,
+        name: &str,
+        function: NativeRunStateFunction,
+    ) -> Result<(), Error> {
+        self.expose(name, Value::NativeRunStateFunction(function))
+    }
+
     pub fn expose_macro(&mut self, name: &str, r#macro: NativeMacro) -> Result<(), Error> {
         self.expose(name, Value::NativeMacro(r#macro))
     }
@@ -0,0 +1,144 @@
+use crate::error::Error;
+use crate::map::{hash_unordered, MapKey};
+use crate::std::logic::order_values;
+use crate::value::Value;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+#[derive(Clone, Default)]
+pub struct Set {
+    // the order elements were first added in, so that iterating is always the
+    // same
+    elements: Vec<MapKey>,
+    lookup: HashSet<MapKey>,
+}
+
+impl Set {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn contains(&self, element: &Value) -> bool {
+        match MapKey::new(element.clone()) {
+            Ok(element) => self.lookup.contains(&element),
+            Err(_) => false,
+        }
+    }
+
+    // gives back false if the element was already in the set
+    pub fn insert(&mut self, element: Value) -> Result<bool, Error> {
+        let element = MapKey::new(element)?;
+
+        if self.lookup.contains(&element) {
+            return Ok(false);
+        }
+
+        self.elements.push(element.clone());
+        self.lookup.insert(element);
+        Ok(true)
+    }
+
+    pub fn remove(&mut self, element: &Value) -> bool {
+        let element = match MapKey::new(element.clone()) {
+            Ok(element) => element,
+            Err(_) => return false,
+        };
+
+        if !self.lookup.remove(&element) {
+            return false;
+        }
+
+        self.elements.retain(|other| *other != element);
+        true
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Value> {
+        self.elements.iter().map(MapKey::get_value)
+    }
+
+    // the elements of self come first in each of these
+    pub fn union(&self, other: &Self) -> Self {
+        let mut output = self.clone();
+        for element in other.elements.iter() {
+            if output.lookup.insert(element.clone()) {
+                output.elements.push(element.clone());
+            }
+        }
+
+        output
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.filter(|element| other.lookup.contains(element))
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.filter(|element| !other.lookup.contains(element))
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.lookup.is_subset(&other.lookup)
+    }
+
+    // keys can not be changed once made, see MapKey::new
+    #[allow(clippy::mutable_key_type)]
+    fn filter(&self, keep: impl Fn(&MapKey) -> bool) -> Self {
+        let elements: Vec<MapKey> = self.elements.iter().filter(|e| keep(e)).cloned().collect();
+        let lookup = elements.iter().cloned().collect();
+
+        Self { elements, lookup }
+    }
+
+    // sorted so that equal sets always look the same
+    fn sorted(&self) -> Vec<&Value> {
+        let mut elements: Vec<&Value> = self.iter().collect();
+        elements.sort_by(|x, y| order_values(x, y));
+        elements
+    }
+}
+
+// the order that elements were added in does not matter
+impl PartialEq for Set {
+    fn eq(&self, other: &Self) -> bool {
+        self.lookup == other.lookup
+    }
+}
+
+impl Hash for Set {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(
+            self.elements
+                .iter()
+                .map(|element| (element.get_normalised(), None)),
+            state,
+        );
+    }
+}
+
+impl fmt::Debug for Set {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.sorted()).finish()
+    }
+}
+
+// written the same way it would be made
+impl fmt::Display for Set {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(make-set")?;
+
+        for element in self.sorted() {
+            write!(f, " {}", element)?;
+        }
+
+        write!(f, ")")
+    }
+}
@@ -1,10 +1,21 @@
 use crate::prelude::*;
+use crate::std::logic::evaluate_condition;
 
 pub fn add_basic_lib(run_state: &mut RunState) -> Result<(), Error> {
     run_state.expose_macro("const", std_basic_const)?;
     run_state.expose_macro("set", std_basic_set)?;
+    run_state.expose_macro("let", std_basic_let)?;
+    run_state.expose_macro("do", std_basic_do)?;
     run_state.expose_macro("if", std_basic_if)?;
     run_state.expose_macro("times", std_basic_times)?;
+    run_state.expose_macro("while", std_basic_while)?;
+    run_state.expose_macro("loop", std_basic_loop)?;
+    run_state.expose_macro("for", std_basic_for)?;
+    run_state.expose_macro("for-each", std_basic_for)?;
+    run_state.expose_macro("break", std_basic_break)?;
+    run_state.expose_macro("continue", std_basic_continue)?;
+    run_state.expose_macro("fn", std_basic_fn)?;
+    run_state.expose_macro("defn", std_basic_defn)?;
     run_state.expose_macro("true", value_true)?;
     run_state.expose_macro("false", value_false)?;
 
@@ -12,13 +23,13 @@ pub fn add_basic_lib(run_state: &mut RunState) -> Result<(), Error> {
 }
 
 // todo: make sure a leaf node
-fn value_true(_: &mut RunState, _node: &Node) -> Result<Value, Error> {
-    Ok(Value::Boolean(true))
+fn value_true(_: &mut RunState, _node: &Node) -> Result<Flow, Error> {
+    Ok(Flow::Value(Value::Boolean(true)))
 }
 
 // todo: make sure a leaf node
-fn value_false(_: &mut RunState, _node: &Node) -> Result<Value, Error> {
-    Ok(Value::Boolean(false))
+fn value_false(_: &mut RunState, _node: &Node) -> Result<Flow, Error> {
+    Ok(Flow::Value(Value::Boolean(false)))
 }
 
 fn set_local(run_state: &mut RunState, node: &Node, is_const: bool) -> Result<(), Error> {
@@ -41,23 +52,19 @@ fn set_local(run_state: &mut RunState, node: &Node, is_const: bool) -> Result<()
             Some(value_node) => {
                 let value = value_node.evaluate(run_state)?;
 
-                // can not put outside the loop due to multiple borrows
-                let scope = run_state.get_local_scope_mut();
-
-                if is_const {
-                    if let Err(mut error) = scope.set_const(identifier, value) {
-                        if let Some(token) = node.get_token().clone() {
-                            error.set_token(token);
-                            return Err(error);
-                        }
-                    }
+                // consts are always defined in the innermost scope whereas set
+                // updates the nearest existing binding
+                let res = if is_const {
+                    run_state.get_local_scope_mut().set_const(identifier, value)
                 } else {
-                    if let Err(mut error) = scope.set_local(identifier, value) {
-                        if let Some(token) = node.get_token().clone() {
-                            error.set_token(token);
-                            return Err(error);
-                        }
+                    run_state.set_local(identifier, value)
+                };
+
+                if let Err(mut error) = res {
+                    if let Some(token) = node.get_token().clone() {
+                        error.set_token(token);
                     }
+                    return Err(error);
                 }
             }
             None => {
@@ -75,21 +82,86 @@ fn set_local(run_state: &mut RunState, node: &Node, is_const: bool) -> Result<()
     Ok(())
 }
 
-fn std_basic_const(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
+fn std_basic_const(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
     let is_const = true;
     set_local(run_state, node, is_const)?;
 
-    Ok(Value::default())
+    Ok(Flow::Value(Value::default()))
 }
 
-fn std_basic_set(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
+fn std_basic_set(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
     let is_const = false;
     set_local(run_state, node, is_const)?;
 
-    Ok(Value::default())
+    Ok(Flow::Value(Value::default()))
+}
+
+pub fn evaluate_block(run_state: &mut RunState, nodes: &[Node]) -> Result<Flow, Error> {
+    let mut last_flow = Flow::Value(Value::default());
+
+    for node in nodes {
+        last_flow = node.evaluate_flow(run_state)?;
+
+        // the rest is skipped by break and continue
+        if !last_flow.is_value() {
+            break;
+        }
+    }
+
+    Ok(last_flow)
+}
+
+fn define_let_bindings(run_state: &mut RunState, bindings: &Node) -> Result<(), Error> {
+    let head = bindings.get_head();
+    let elements: Vec<&Node> = head.iter().chain(bindings.get_children()).collect();
+
+    if !elements.len().is_multiple_of(2) {
+        return Err(Error::new(
+            "bindings must be identifier/value pairs".to_string(),
+            bindings.get_token().clone(),
+        ));
+    }
+
+    for pair in elements.chunks(2) {
+        let identifier = get_identifier(pair[0])?;
+
+        // each binding can see the ones before it
+        let value = pair[1].evaluate(run_state)?;
+        run_state
+            .get_local_scope_mut()
+            .define_local(identifier, value);
+    }
+
+    Ok(())
+}
+
+fn std_basic_let(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    let args = node.get_children();
+
+    if args.is_empty() {
+        return Err(Error::new(
+            "requires a list of bindings".to_string(),
+            node.get_token().clone(),
+        ));
+    }
+
+    run_state.push_scope();
+    let res = define_let_bindings(run_state, &args[0])
+        .and_then(|_| evaluate_block(run_state, &args[1..]));
+    run_state.pop_scope();
+
+    res
+}
+
+fn std_basic_do(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    run_state.push_scope();
+    let res = evaluate_block(run_state, node.get_children());
+    run_state.pop_scope();
+
+    res
 }
 
-fn std_basic_if(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
+fn std_basic_if(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
     let args = node.get_children();
 
     if args.len() < 2 {
@@ -123,13 +195,13 @@ fn std_basic_if(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
                             }
                             _ => {
                                 // must be an expression
-                                return node.evaluate(run_state);
+                                return node.evaluate_flow(run_state);
                             }
                         }
                     }
                     _ => {
                         // must be the body of the if block
-                        return node.evaluate(run_state);
+                        return node.evaluate_flow(run_state);
                     }
                 }
             }
@@ -151,38 +223,293 @@ fn std_basic_if(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
                 if_mode = IfMode::Normal;
             }
             IfMode::Body => {
-                return node.evaluate(run_state);
+                return node.evaluate_flow(run_state);
             }
         }
     }
 
+    Ok(Flow::Value(Value::default()))
+}
+
+enum LoopBody {
+    Value(Value),
+    Continue,
+    Break(Value),
+}
+
+fn evaluate_loop_body(run_state: &mut RunState, nodes: &[Node]) -> Result<LoopBody, Error> {
+    match evaluate_block(run_state, nodes)? {
+        Flow::Break(value) => Ok(LoopBody::Break(value)),
+        Flow::Continue => Ok(LoopBody::Continue),
+        flow => Ok(LoopBody::Value(flow.into_value(run_state)?)),
+    }
+}
+
+fn run_times(
+    run_state: &mut RunState,
+    count: i64,
+    index_identifier: Option<&String>,
+    body: &[Node],
+) -> Result<Value, Error> {
+    for i in 0..count {
+        if let Some(identifier) = index_identifier {
+            // a new variable each time so closures keep their own index
+            run_state
+                .get_local_scope_mut()
+                .define_local(identifier, Value::Integer(i));
+        }
+
+        if let LoopBody::Break(value) = evaluate_loop_body(run_state, body)? {
+            return Ok(value);
+        }
+    }
+
     Ok(Value::default())
 }
 
-fn std_basic_times(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
+fn std_basic_times(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    let args = node.get_children();
+
+    let index_identifier = match args.len() {
+        2 => None,
+        3 => Some(get_identifier(&args[1])?),
+        _ => {
+            return Err(Error::new(
+                "takes two or three arguments".to_string(),
+                node.get_token().clone(),
+            ));
+        }
+    };
+
+    let count_value = args.first().unwrap().evaluate(run_state)?;
+    match count_value {
+        Value::Integer(count) => {
+            let body = std::slice::from_ref(args.last().unwrap());
+
+            // only need a new scope if there is an index to put in it
+            match index_identifier {
+                Some(_) => {
+                    run_state.push_scope();
+                    let res = run_times(run_state, count, index_identifier, body);
+                    run_state.pop_scope();
+
+                    res.map(Flow::Value)
+                }
+                None => run_times(run_state, count, None, body).map(Flow::Value),
+            }
+        }
+        _ => Err(Error::new(
+            format!("count must be an integer, recieved: {:?}", count_value),
+            node.get_token().clone(),
+        )),
+    }
+}
+
+fn std_basic_while(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
     let args = node.get_children();
 
-    if args.len() != 2 {
+    if args.is_empty() {
         return Err(Error::new(
-            "takes two arguments".to_string(),
+            "requires a condition".to_string(),
             node.get_token().clone(),
         ));
     }
 
-    let count_value = args.first().unwrap().evaluate(run_state)?;
-    match count_value {
-        Value::Integer(count) => {
-            for _ in 0..count {
-                args.last().unwrap().evaluate(run_state)?;
+    while evaluate_condition(run_state, &args[0])? {
+        if let LoopBody::Break(value) = evaluate_loop_body(run_state, &args[1..])? {
+            return Ok(Flow::Value(value));
+        }
+    }
+
+    Ok(Flow::Value(Value::default()))
+}
+
+fn std_basic_loop(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    loop {
+        if let LoopBody::Break(value) = evaluate_loop_body(run_state, node.get_children())? {
+            return Ok(Flow::Value(value));
+        }
+    }
+}
+
+fn std_basic_for(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    let args = node.get_children();
+
+    if args.len() < 2 {
+        return Err(Error::new(
+            "requires an identifier and something to iterate over".to_string(),
+            node.get_token().clone(),
+        ));
+    }
+
+    let identifier = get_identifier(&args[0])?;
+    let iterable = args[1].evaluate(run_state)?;
+
+    // "(for x xs collect body)" gives back a list of the body results
+    let is_collect = match args.get(2) {
+        Some(arg) if !arg.is_bracketed() && arg.get_children().is_empty() => {
+            matches!(get_identifier(arg), Ok(keyword) if keyword == "collect")
+        }
+        _ => false,
+    };
+    let body = if is_collect { &args[3..] } else { &args[2..] };
+
+    let elements = match iterable.iter() {
+        Some(elements) => elements,
+        None => {
+            return Err(Error::new(
+                format!("can not iterate over: {:?}", iterable),
+                args[1].get_token().clone(),
+            ));
+        }
+    };
+
+    let mut collected = Vec::new();
+    for element in elements {
+        // each iteration gets a fresh scope so closures keep their own element
+        run_state.push_scope();
+        run_state
+            .get_local_scope_mut()
+            .define_local(identifier, element);
+        let res = evaluate_loop_body(run_state, body);
+        run_state.pop_scope();
+
+        match res? {
+            LoopBody::Value(value) => {
+                if is_collect {
+                    collected.push(value);
+                }
+            }
+            LoopBody::Continue => {}
+            LoopBody::Break(value) => {
+                // when collecting, breaking just stops early
+                if is_collect {
+                    break;
+                }
+
+                return Ok(Flow::Value(value));
             }
         }
+    }
+
+    if is_collect {
+        Ok(Flow::Value(Value::List(collected)))
+    } else {
+        Ok(Flow::Value(Value::default()))
+    }
+}
+
+fn std_basic_break(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    let args = node.get_children();
+
+    let value = match args.len() {
+        0 => Value::default(),
+        1 => args[0].evaluate(run_state)?,
         _ => {
             return Err(Error::new(
-                format!("count must be an integer, recieved: {:?}", count_value),
+                "takes at most one argument".to_string(),
                 node.get_token().clone(),
             ));
         }
+    };
+
+    Ok(Flow::Break(value))
+}
+
+fn std_basic_continue(_: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    if !node.get_children().is_empty() {
+        return Err(Error::new(
+            "takes no arguments".to_string(),
+            node.get_token().clone(),
+        ));
+    }
+
+    Ok(Flow::Continue)
+}
+
+pub fn create_function(
+    run_state: &mut RunState,
+    parameters_node: &Node,
+    body_nodes: &[Node],
+) -> Result<Function, Error> {
+    let parameters = get_identifier_list(parameters_node)?;
+
+    let mut body = match body_nodes {
+        [body_node] => body_node.clone(),
+        _ => {
+            let mut body = Node::new(None);
+            for body_node in body_nodes {
+                body.add_child(body_node.clone());
+            }
+            body
+        }
+    };
+    body.mark_tail_position();
+
+    Function::new(parameters, body, run_state.capture_scope())
+}
+
+fn std_basic_fn(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    let args = node.get_children();
+
+    if args.is_empty() {
+        return Err(Error::new(
+            "requires a parameter list".to_string(),
+            node.get_token().clone(),
+        ));
     }
 
-    Ok(Value::default())
+    match create_function(run_state, &args[0], &args[1..]) {
+        Ok(function) => Ok(Flow::Value(Value::Function(function))),
+        Err(mut error) => {
+            if let Some(token) = node.get_token().clone() {
+                error.set_token(token);
+            }
+            Err(error)
+        }
+    }
+}
+
+// shared by defn and defmacro, which only differ in the kind of value defined
+pub fn define_function(
+    run_state: &mut RunState,
+    node: &Node,
+    to_value: fn(Function) -> Value,
+) -> Result<Flow, Error> {
+    let args = node.get_children();
+
+    if args.len() < 2 {
+        return Err(Error::new(
+            "requires an identifier and a parameter list".to_string(),
+            node.get_token().clone(),
+        ));
+    }
+
+    let identifier = get_identifier(&args[0])?;
+
+    // define the identifier before capturing the scope so that the function
+    // can refer to itself
+    let res = run_state
+        .get_local_scope_mut()
+        .set_local(identifier, Value::default())
+        .and_then(|_| create_function(run_state, &args[1], &args[2..]))
+        .and_then(|function| {
+            run_state
+                .get_local_scope_mut()
+                .set_local(identifier, to_value(function))
+        });
+
+    match res {
+        Ok(()) => Ok(Flow::Value(Value::default())),
+        Err(mut error) => {
+            if let Some(token) = node.get_token().clone() {
+                error.set_token(token);
+            }
+            Err(error)
+        }
+    }
+}
+
+fn std_basic_defn(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    define_function(run_state, node, Value::Function)
 }
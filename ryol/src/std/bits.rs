@@ -0,0 +1,198 @@
+use crate::prelude::*;
+use crate::std::maths::{from_big_integer, get_argument_count_error, to_big_integer};
+use num_bigint::BigInt;
+
+pub fn add_bits_lib(run_state: &mut RunState) -> Result<(), Error> {
+    run_state.expose_function("bit-and", std_bits_and)?;
+    run_state.expose_function("bit-or", std_bits_or)?;
+    run_state.expose_function("bit-xor", std_bits_xor)?;
+    run_state.expose_function("bit-not", std_bits_not)?;
+
+    // shr keeps the sign, shr-logical fills with zeros and only works on the
+    // 64 bits of an integer
+    run_state.expose_function("shl", std_bits_shl)?;
+    run_state.expose_function("shr", std_bits_shr)?;
+    run_state.expose_function("shr-logical", std_bits_shr_logical)?;
+
+    run_state.expose_function("popcount", std_bits_popcount)?;
+    run_state.expose_function("leading-zeros", std_bits_leading_zeros)?;
+    run_state.expose_function("trailing-zeros", std_bits_trailing_zeros)?;
+
+    run_state.expose_function("bit-test", std_bits_test)?;
+    run_state.expose_function("bit-set", std_bits_set)?;
+    run_state.expose_function("bit-clear", std_bits_clear)?;
+
+    Ok(())
+}
+
+// the largest amount shl shifts by, so that a typo can not use up all memory
+const MAX_SHIFT: usize = 1 << 20;
+
+fn get_non_integer_type_error(function_name: &str, arg: &Value) -> Error {
+    Error::new(
+        format!(
+            "non integer type: {:?} in function \"{}\"",
+            arg, function_name
+        ),
+        None,
+    )
+}
+
+fn get_integer(function_name: &str, arg: &Value) -> Result<i64, Error> {
+    match arg {
+        Value::Integer(integer) => Ok(*integer),
+        _ => Err(get_non_integer_type_error(function_name, arg)),
+    }
+}
+
+fn get_single_integer(function_name: &str, args: &[Value]) -> Result<i64, Error> {
+    match args {
+        [arg] => get_integer(function_name, arg),
+        _ => Err(get_argument_count_error(function_name, "one argument")),
+    }
+}
+
+// integers are 64 bits wide so only positions 0 to 63 exist
+fn get_integer_and_position(function_name: &str, args: &[Value]) -> Result<(i64, u32), Error> {
+    let (integer, position) = match args {
+        [integer, position] => (
+            get_integer(function_name, integer)?,
+            get_integer(function_name, position)?,
+        ),
+        _ => {
+            return Err(get_argument_count_error(function_name, "two arguments"));
+        }
+    };
+
+    if !(0..i64::BITS as i64).contains(&position) {
+        return Err(Error::new(
+            format!(
+                "bit position: {} is out of range in function \"{}\"",
+                position, function_name
+            ),
+            None,
+        ));
+    }
+
+    Ok((integer, position as u32))
+}
+
+fn fold_integers(
+    function_name: &str,
+    args: &[Value],
+    operator: fn(i64, i64) -> i64,
+) -> Result<Value, Error> {
+    let (first, rest) = match args.split_first() {
+        Some(parts) => parts,
+        None => {
+            return Err(Error::new(
+                format!(
+                    "function \"{}\" requires at least one argument",
+                    function_name
+                ),
+                None,
+            ));
+        }
+    };
+
+    let mut res = get_integer(function_name, first)?;
+    for arg in rest {
+        res = operator(res, get_integer(function_name, arg)?);
+    }
+
+    Ok(Value::Integer(res))
+}
+
+fn std_bits_and(args: Vec<Value>) -> Result<Value, Error> {
+    fold_integers("bit-and", &args, |x, y| x & y)
+}
+
+fn std_bits_or(args: Vec<Value>) -> Result<Value, Error> {
+    fold_integers("bit-or", &args, |x, y| x | y)
+}
+
+fn std_bits_xor(args: Vec<Value>) -> Result<Value, Error> {
+    fold_integers("bit-xor", &args, |x, y| x ^ y)
+}
+
+fn std_bits_not(args: Vec<Value>) -> Result<Value, Error> {
+    Ok(Value::Integer(!get_single_integer("bit-not", &args)?))
+}
+
+// shl and shr work on the exact value, so shl grows into a big integer rather
+// than dropping the bits shifted out
+fn get_shift_args(function_name: &str, args: &[Value]) -> Result<(BigInt, usize), Error> {
+    let (integer, amount) = match args {
+        [integer, amount] => match to_big_integer(integer) {
+            Some(integer) => (integer, get_integer(function_name, amount)?),
+            None => return Err(get_non_integer_type_error(function_name, integer)),
+        },
+        _ => return Err(get_argument_count_error(function_name, "two arguments")),
+    };
+
+    if amount < 0 {
+        return Err(Error::new(
+            format!(
+                "shift amount: {} is negative in function \"{}\"",
+                amount, function_name
+            ),
+            None,
+        ));
+    }
+
+    Ok((integer, amount as usize))
+}
+
+fn std_bits_shl(args: Vec<Value>) -> Result<Value, Error> {
+    let (integer, amount) = get_shift_args("shl", &args)?;
+
+    if amount > MAX_SHIFT {
+        return Err(Error::new(
+            format!("shift amount: {} is too large in function \"shl\"", amount),
+            None,
+        ));
+    }
+
+    Ok(from_big_integer(integer << amount))
+}
+
+// rounds towards negative infinity like shifting an i64 does
+fn std_bits_shr(args: Vec<Value>) -> Result<Value, Error> {
+    let (integer, amount) = get_shift_args("shr", &args)?;
+    Ok(from_big_integer(integer >> amount))
+}
+
+fn std_bits_shr_logical(args: Vec<Value>) -> Result<Value, Error> {
+    let (integer, amount) = get_integer_and_position("shr-logical", &args)?;
+    Ok(Value::Integer(((integer as u64) >> amount) as i64))
+}
+
+fn std_bits_popcount(args: Vec<Value>) -> Result<Value, Error> {
+    let integer = get_single_integer("popcount", &args)?;
+    Ok(Value::Integer(integer.count_ones() as i64))
+}
+
+fn std_bits_leading_zeros(args: Vec<Value>) -> Result<Value, Error> {
+    let integer = get_single_integer("leading-zeros", &args)?;
+    Ok(Value::Integer(integer.leading_zeros() as i64))
+}
+
+fn std_bits_trailing_zeros(args: Vec<Value>) -> Result<Value, Error> {
+    let integer = get_single_integer("trailing-zeros", &args)?;
+    Ok(Value::Integer(integer.trailing_zeros() as i64))
+}
+
+fn std_bits_test(args: Vec<Value>) -> Result<Value, Error> {
+    let (integer, position) = get_integer_and_position("bit-test", &args)?;
+    Ok(Value::Boolean(integer & (1 << position) != 0))
+}
+
+fn std_bits_set(args: Vec<Value>) -> Result<Value, Error> {
+    let (integer, position) = get_integer_and_position("bit-set", &args)?;
+    Ok(Value::Integer(integer | (1 << position)))
+}
+
+fn std_bits_clear(args: Vec<Value>) -> Result<Value, Error> {
+    let (integer, position) = get_integer_and_position("bit-clear", &args)?;
+    Ok(Value::Integer(integer & !(1 << position)))
+}
@@ -0,0 +1,392 @@
+use crate::collections::contains_collection;
+use crate::map::MapKey;
+use crate::prelude::*;
+use crate::std::list::call_value;
+use crate::std::maths::get_argument_count_error;
+
+pub fn add_collections_lib(run_state: &mut RunState) -> Result<(), Error> {
+    run_state.expose_function("make-deque", std_collections_make_deque)?;
+    run_state.expose_function("deque?", std_collections_is_deque)?;
+    run_state.expose_function("push-front", std_collections_push_front)?;
+    run_state.expose_function("push-back", std_collections_push_back)?;
+    run_state.expose_function("pop-front", std_collections_pop_front)?;
+    run_state.expose_function("pop-back", std_collections_pop_back)?;
+    run_state.expose_function("peek-front", std_collections_peek_front)?;
+    run_state.expose_function("peek-back", std_collections_peek_back)?;
+
+    run_state.expose_function("make-heap", std_collections_make_heap)?;
+    run_state.expose_function("heap?", std_collections_is_heap)?;
+    // may need to call the key function
+    run_state.expose_run_state_function("heap-push", std_collections_heap_push)?;
+    run_state.expose_function("heap-pop", std_collections_heap_pop)?;
+    run_state.expose_function("heap-peek", std_collections_heap_peek)?;
+
+    run_state.expose_function("make-sorted-map", std_collections_make_sorted_map)?;
+    run_state.expose_function("sorted-map?", std_collections_is_sorted_map)?;
+    run_state.expose_function("sorted-map-get", std_collections_sorted_map_get)?;
+    run_state.expose_function("sorted-map-set", std_collections_sorted_map_set)?;
+    run_state.expose_function("sorted-map-remove", std_collections_sorted_map_remove)?;
+    run_state.expose_function("sorted-map-has?", std_collections_sorted_map_has)?;
+    run_state.expose_function("sorted-map-range", std_collections_sorted_map_range)?;
+    run_state.expose_function("sorted-map-first", std_collections_sorted_map_first)?;
+    run_state.expose_function("sorted-map-last", std_collections_sorted_map_last)?;
+    run_state.expose_function("sorted-map-pop-first", std_collections_sorted_map_pop_first)?;
+    run_state.expose_function("sorted-map-pop-last", std_collections_sorted_map_pop_last)?;
+
+    Ok(())
+}
+
+fn get_empty_error(function_name: &str, collection_name: &str) -> Error {
+    Error::new(
+        format!(
+            "can not use an empty {} in function \"{}\"",
+            collection_name, function_name
+        ),
+        None,
+    )
+}
+
+// priorities and sorted map keys are ordered with order_values, so have the
+// same restrictions as map keys and are detached in the same way
+fn get_key(function_name: &str, key: &Value) -> Result<Value, Error> {
+    match MapKey::new(key.clone()) {
+        Ok(key) => Ok(key.get_value().clone()),
+        Err(_) => Err(Error::new(
+            format!(
+                "can not use: {:?} as a key in function \"{}\"",
+                key, function_name
+            ),
+            None,
+        )),
+    }
+}
+
+fn check_not_self_containing(
+    function_name: &str,
+    collection: Value,
+    element: &Value,
+) -> Result<(), Error> {
+    if contains_collection(element, &collection) {
+        return Err(Error::new(
+            format!(
+                "can not add a collection to itself in function \"{}\"",
+                function_name
+            ),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+fn entry_to_value((key, value): (Value, Value)) -> Value {
+    Value::List(vec![key, value])
+}
+
+fn get_deque<'a>(
+    function_name: &str,
+    args: &'a [Value],
+) -> Result<(&'a Deque, &'a [Value]), Error> {
+    match args.split_first() {
+        Some((Value::Deque(deque), rest)) => Ok((deque, rest)),
+        Some((arg, _)) => Err(Error::new(
+            format!(
+                "non deque type: {:?} in function \"{}\"",
+                arg, function_name
+            ),
+            None,
+        )),
+        None => Err(get_argument_count_error(
+            function_name,
+            "a deque as its first argument",
+        )),
+    }
+}
+
+fn get_single_deque<'a>(function_name: &str, args: &'a [Value]) -> Result<&'a Deque, Error> {
+    match get_deque(function_name, args)? {
+        (deque, []) => Ok(deque),
+        _ => Err(get_argument_count_error(function_name, "one argument")),
+    }
+}
+
+fn std_collections_make_deque(args: Vec<Value>) -> Result<Value, Error> {
+    Ok(Value::Deque(Deque::new(args)))
+}
+
+fn std_collections_is_deque(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [arg] => Ok(Value::Boolean(matches!(arg, Value::Deque(_)))),
+        _ => Err(get_argument_count_error("deque?", "one argument")),
+    }
+}
+
+// each push changes the deque in place and gives it back
+fn std_collections_push_front(args: Vec<Value>) -> Result<Value, Error> {
+    let (deque, elements) = get_deque("push-front", &args)?;
+
+    for element in elements {
+        check_not_self_containing("push-front", Value::Deque(deque.clone()), element)?;
+        deque.push_front(element.clone());
+    }
+    Ok(Value::Deque(deque.clone()))
+}
+
+fn std_collections_push_back(args: Vec<Value>) -> Result<Value, Error> {
+    let (deque, elements) = get_deque("push-back", &args)?;
+
+    for element in elements {
+        check_not_self_containing("push-back", Value::Deque(deque.clone()), element)?;
+        deque.push_back(element.clone());
+    }
+    Ok(Value::Deque(deque.clone()))
+}
+
+fn std_collections_pop_front(args: Vec<Value>) -> Result<Value, Error> {
+    get_single_deque("pop-front", &args)?
+        .pop_front()
+        .ok_or_else(|| get_empty_error("pop-front", "deque"))
+}
+
+fn std_collections_pop_back(args: Vec<Value>) -> Result<Value, Error> {
+    get_single_deque("pop-back", &args)?
+        .pop_back()
+        .ok_or_else(|| get_empty_error("pop-back", "deque"))
+}
+
+fn std_collections_peek_front(args: Vec<Value>) -> Result<Value, Error> {
+    get_single_deque("peek-front", &args)?
+        .front()
+        .ok_or_else(|| get_empty_error("peek-front", "deque"))
+}
+
+fn std_collections_peek_back(args: Vec<Value>) -> Result<Value, Error> {
+    get_single_deque("peek-back", &args)?
+        .back()
+        .ok_or_else(|| get_empty_error("peek-back", "deque"))
+}
+
+fn get_heap<'a>(
+    function_name: &str,
+    args: &'a [Value],
+) -> Result<(&'a PriorityQueue, &'a [Value]), Error> {
+    match args.split_first() {
+        Some((Value::PriorityQueue(queue), rest)) => Ok((queue, rest)),
+        Some((arg, _)) => Err(Error::new(
+            format!("non heap type: {:?} in function \"{}\"", arg, function_name),
+            None,
+        )),
+        None => Err(get_argument_count_error(
+            function_name,
+            "a heap as its first argument",
+        )),
+    }
+}
+
+fn get_single_heap<'a>(function_name: &str, args: &'a [Value]) -> Result<&'a PriorityQueue, Error> {
+    match get_heap(function_name, args)? {
+        (queue, []) => Ok(queue),
+        _ => Err(get_argument_count_error(function_name, "one argument")),
+    }
+}
+
+// the optional key function gives the priority of each element, otherwise
+// elements are their own priority
+fn std_collections_make_heap(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [] => Ok(Value::PriorityQueue(PriorityQueue::new(None))),
+        [key_function @ (Value::Function(_)
+        | Value::NativeFunction(_)
+        | Value::NativeRunStateFunction(_))] => Ok(Value::PriorityQueue(PriorityQueue::new(Some(
+            key_function.clone(),
+        )))),
+        [arg] => Err(Error::new(
+            format!("non function type: {:?} in function \"make-heap\"", arg),
+            None,
+        )),
+        _ => Err(get_argument_count_error(
+            "make-heap",
+            "an optional key function",
+        )),
+    }
+}
+
+fn std_collections_is_heap(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [arg] => Ok(Value::Boolean(matches!(arg, Value::PriorityQueue(_)))),
+        _ => Err(get_argument_count_error("heap?", "one argument")),
+    }
+}
+
+fn std_collections_heap_push(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let (queue, elements) = get_heap("heap-push", &args)?;
+
+    let key_function = queue.get_key_function();
+    for element in elements {
+        check_not_self_containing("heap-push", Value::PriorityQueue(queue.clone()), element)?;
+        let priority = match &key_function {
+            Some(key_function) => {
+                call_value(run_state, "heap-push", key_function, vec![element.clone()])?
+            }
+            None => element.clone(),
+        };
+
+        queue.push(get_key("heap-push", &priority)?, element.clone());
+    }
+    Ok(Value::PriorityQueue(queue.clone()))
+}
+
+fn std_collections_heap_pop(args: Vec<Value>) -> Result<Value, Error> {
+    get_single_heap("heap-pop", &args)?
+        .pop()
+        .ok_or_else(|| get_empty_error("heap-pop", "heap"))
+}
+
+fn std_collections_heap_peek(args: Vec<Value>) -> Result<Value, Error> {
+    get_single_heap("heap-peek", &args)?
+        .peek()
+        .ok_or_else(|| get_empty_error("heap-peek", "heap"))
+}
+
+fn get_sorted_map<'a>(
+    function_name: &str,
+    args: &'a [Value],
+) -> Result<(&'a SortedMap, &'a [Value]), Error> {
+    match args.split_first() {
+        Some((Value::SortedMap(map), rest)) => Ok((map, rest)),
+        Some((arg, _)) => Err(Error::new(
+            format!(
+                "non sorted map type: {:?} in function \"{}\"",
+                arg, function_name
+            ),
+            None,
+        )),
+        None => Err(get_argument_count_error(
+            function_name,
+            "a sorted map as its first argument",
+        )),
+    }
+}
+
+fn get_single_sorted_map<'a>(
+    function_name: &str,
+    args: &'a [Value],
+) -> Result<&'a SortedMap, Error> {
+    match get_sorted_map(function_name, args)? {
+        (map, []) => Ok(map),
+        _ => Err(get_argument_count_error(function_name, "one argument")),
+    }
+}
+
+// takes keys and values one after the other, like make-map
+fn std_collections_make_sorted_map(args: Vec<Value>) -> Result<Value, Error> {
+    if !args.len().is_multiple_of(2) {
+        return Err(get_argument_count_error(
+            "make-sorted-map",
+            "pairs of keys and values",
+        ));
+    }
+
+    let map = SortedMap::new();
+    for pair in args.chunks(2) {
+        map.insert(get_key("make-sorted-map", &pair[0])?, pair[1].clone());
+    }
+    Ok(Value::SortedMap(map))
+}
+
+fn std_collections_is_sorted_map(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [arg] => Ok(Value::Boolean(matches!(arg, Value::SortedMap(_)))),
+        _ => Err(get_argument_count_error("sorted-map?", "one argument")),
+    }
+}
+
+// gives back the default, or null, when the key is missing
+fn std_collections_sorted_map_get(args: Vec<Value>) -> Result<Value, Error> {
+    match get_sorted_map("sorted-map-get", &args)? {
+        (map, [key]) => Ok(map.get(key).unwrap_or_default()),
+        (map, [key, default]) => Ok(map.get(key).unwrap_or_else(|| default.clone())),
+        _ => Err(get_argument_count_error(
+            "sorted-map-get",
+            "a sorted map, a key and an optional default",
+        )),
+    }
+}
+
+fn std_collections_sorted_map_set(args: Vec<Value>) -> Result<Value, Error> {
+    match get_sorted_map("sorted-map-set", &args)? {
+        (map, [key, value]) => {
+            check_not_self_containing("sorted-map-set", Value::SortedMap(map.clone()), value)?;
+            map.insert(get_key("sorted-map-set", key)?, value.clone());
+            Ok(Value::SortedMap(map.clone()))
+        }
+        _ => Err(get_argument_count_error(
+            "sorted-map-set",
+            "a sorted map, a key and a value",
+        )),
+    }
+}
+
+// gives back the removed value, or null if there was not one
+fn std_collections_sorted_map_remove(args: Vec<Value>) -> Result<Value, Error> {
+    match get_sorted_map("sorted-map-remove", &args)? {
+        (map, [key]) => Ok(map.remove(key).unwrap_or_default()),
+        _ => Err(get_argument_count_error(
+            "sorted-map-remove",
+            "a sorted map and a key",
+        )),
+    }
+}
+
+fn std_collections_sorted_map_has(args: Vec<Value>) -> Result<Value, Error> {
+    match get_sorted_map("sorted-map-has?", &args)? {
+        (map, [key]) => Ok(Value::Boolean(map.get(key).is_some())),
+        _ => Err(get_argument_count_error(
+            "sorted-map-has?",
+            "a sorted map and a key",
+        )),
+    }
+}
+
+// the entries with keys from start up to but not including end
+fn std_collections_sorted_map_range(args: Vec<Value>) -> Result<Value, Error> {
+    match get_sorted_map("sorted-map-range", &args)? {
+        (map, [start, end]) => Ok(Value::List(
+            map.range(start, end)
+                .into_iter()
+                .map(entry_to_value)
+                .collect(),
+        )),
+        _ => Err(get_argument_count_error(
+            "sorted-map-range",
+            "a sorted map, a start key and an end key",
+        )),
+    }
+}
+
+fn std_collections_sorted_map_first(args: Vec<Value>) -> Result<Value, Error> {
+    get_single_sorted_map("sorted-map-first", &args)?
+        .first()
+        .map(entry_to_value)
+        .ok_or_else(|| get_empty_error("sorted-map-first", "sorted map"))
+}
+
+fn std_collections_sorted_map_last(args: Vec<Value>) -> Result<Value, Error> {
+    get_single_sorted_map("sorted-map-last", &args)?
+        .last()
+        .map(entry_to_value)
+        .ok_or_else(|| get_empty_error("sorted-map-last", "sorted map"))
+}
+
+fn std_collections_sorted_map_pop_first(args: Vec<Value>) -> Result<Value, Error> {
+    get_single_sorted_map("sorted-map-pop-first", &args)?
+        .pop_first()
+        .map(entry_to_value)
+        .ok_or_else(|| get_empty_error("sorted-map-pop-first", "sorted map"))
+}
+
+fn std_collections_sorted_map_pop_last(args: Vec<Value>) -> Result<Value, Error> {
+    get_single_sorted_map("sorted-map-pop-last", &args)?
+        .pop_last()
+        .map(entry_to_value)
+        .ok_or_else(|| get_empty_error("sorted-map-pop-last", "sorted map"))
+}
@@ -0,0 +1,378 @@
+use crate::prelude::*;
+use crate::std::maths::to_float;
+
+// templates for "fmt", "printf", "print" and "println" look like rust format
+// strings:
+//
+//   {} {0} {name} {:spec} {0:spec} {name:spec}
+//
+// empty placeholders take the next argument, numbers pick an argument and
+// names take a member of a structure passed as the first argument. "{{" and
+// "}}" are a literal "{" and "}". specs are written as:
+//
+//   [[fill]align][+][#][0][width][.precision][type]
+//
+// where align is one of "<", ">" or "^", type is one of "x", "X", "o" or "b"
+// for integers in another radix and "#" adds a "0x", "0o" or "0b" prefix.
+// numbers are aligned to the right and everything else to the left. "format"
+// does not use templates, it joins its arguments together, and "print" and
+// "println" only use one when the first argument has a brace in it
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Align {
+    Left,
+    Right,
+    Centre,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Radix {
+    LowerHex,
+    UpperHex,
+    Octal,
+    Binary,
+}
+
+#[derive(Debug, Default)]
+struct Spec {
+    fill: Option<char>,
+    align: Option<Align>,
+    plus: bool,
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+    radix: Option<Radix>,
+}
+
+enum Placeholder<'a> {
+    Next,
+    Index(usize),
+    Name(&'a str),
+}
+
+fn get_format_error(message: &str, template: &str) -> Error {
+    Error::new(
+        format!("{} in format string: \"{}\"", message, template),
+        None,
+    )
+}
+
+fn get_align(c: char) -> Option<Align> {
+    match c {
+        '<' => Some(Align::Left),
+        '>' => Some(Align::Right),
+        '^' => Some(Align::Centre),
+        _ => None,
+    }
+}
+
+// reads digits from the front of the chars if there are any
+fn parse_number(chars: &[char], i: &mut usize) -> Option<usize> {
+    let start = *i;
+    while *i < chars.len() && chars[*i].is_ascii_digit() {
+        *i += 1;
+    }
+
+    chars[start..*i].iter().collect::<String>().parse().ok()
+}
+
+fn parse_spec(spec: &str, template: &str) -> Result<Spec, Error> {
+    let chars: Vec<char> = spec.chars().collect();
+    let mut output = Spec::default();
+    let mut i = 0;
+
+    if let Some(align) = chars.get(1).and_then(|c| get_align(*c)) {
+        output.fill = Some(chars[0]);
+        output.align = Some(align);
+        i = 2;
+    } else if let Some(align) = chars.first().and_then(|c| get_align(*c)) {
+        output.align = Some(align);
+        i = 1;
+    }
+
+    if chars.get(i) == Some(&'+') {
+        output.plus = true;
+        i += 1;
+    }
+    if chars.get(i) == Some(&'#') {
+        output.alternate = true;
+        i += 1;
+    }
+    if chars.get(i) == Some(&'0') {
+        output.zero = true;
+        i += 1;
+    }
+
+    output.width = parse_number(&chars, &mut i).unwrap_or(0);
+
+    if chars.get(i) == Some(&'.') {
+        i += 1;
+        output.precision = match parse_number(&chars, &mut i) {
+            Some(precision) => Some(precision),
+            None => return Err(get_format_error("missing precision", template)),
+        };
+    }
+
+    output.radix = match chars.get(i) {
+        Some('x') => Some(Radix::LowerHex),
+        Some('X') => Some(Radix::UpperHex),
+        Some('o') => Some(Radix::Octal),
+        Some('b') => Some(Radix::Binary),
+        _ => None,
+    };
+    if output.radix.is_some() {
+        i += 1;
+    }
+
+    if i != chars.len() {
+        return Err(get_format_error(
+            &format!("invalid format spec: \"{}\"", spec),
+            template,
+        ));
+    }
+
+    Ok(output)
+}
+
+fn parse_placeholder<'a>(
+    placeholder: &'a str,
+    template: &str,
+) -> Result<(Placeholder<'a>, Spec), Error> {
+    let (argument, spec) = match placeholder.split_once(':') {
+        Some((argument, spec)) => (argument, parse_spec(spec, template)?),
+        None => (placeholder, Spec::default()),
+    };
+
+    let argument = if argument.is_empty() {
+        Placeholder::Next
+    } else if let Ok(index) = argument.parse() {
+        Placeholder::Index(index)
+    } else if !argument.contains(char::is_whitespace) {
+        Placeholder::Name(argument)
+    } else {
+        return Err(get_format_error(
+            &format!("invalid placeholder: \"{{{}}}\"", placeholder),
+            template,
+        ));
+    };
+
+    Ok((argument, spec))
+}
+
+fn is_number(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Integer(_) | Value::BigInteger(_) | Value::Rational(_) | Value::Float(_)
+    )
+}
+
+// splits a number into its sign, radix prefix and digits
+fn format_number(
+    value: &Value,
+    spec: &Spec,
+    template: &str,
+) -> Result<(bool, String, String), Error> {
+    if let Some(radix) = spec.radix {
+        let integer = match value {
+            Value::Integer(integer) => BigInt::from(*integer),
+            Value::BigInteger(big_integer) => big_integer.clone(),
+            _ => {
+                return Err(get_format_error(
+                    &format!("can only format integers in a radix, recieved: {:?}", value),
+                    template,
+                ));
+            }
+        };
+
+        let magnitude = integer.magnitude();
+        let (prefix, digits) = match radix {
+            Radix::LowerHex => ("0x", format!("{:x}", magnitude)),
+            Radix::UpperHex => ("0x", format!("{:X}", magnitude)),
+            Radix::Octal => ("0o", format!("{:o}", magnitude)),
+            Radix::Binary => ("0b", format!("{:b}", magnitude)),
+        };
+
+        let prefix = if spec.alternate { prefix } else { "" };
+        return Ok((
+            integer.sign() == num_bigint::Sign::Minus,
+            prefix.to_string(),
+            digits,
+        ));
+    }
+
+    let formatted = match spec.precision {
+        // every number is a float once it has a precision
+        Some(precision) => format!("{:.*}", precision, to_float(value).unwrap()),
+        None => value.to_string(),
+    };
+
+    Ok(match formatted.strip_prefix('-') {
+        Some(digits) => (true, String::new(), digits.to_string()),
+        None => (false, String::new(), formatted),
+    })
+}
+
+fn pad(output: &mut String, body: &str, spec: &Spec, default_align: Align) {
+    let padding = spec.width.saturating_sub(body.chars().count());
+    let fill = spec.fill.unwrap_or(' ');
+
+    let (before, after) = match spec.align.unwrap_or(default_align) {
+        Align::Left => (0, padding),
+        Align::Right => (padding, 0),
+        Align::Centre => (padding / 2, padding - padding / 2),
+    };
+
+    output.extend(::std::iter::repeat_n(fill, before));
+    output.push_str(body);
+    output.extend(::std::iter::repeat_n(fill, after));
+}
+
+fn format_value(
+    output: &mut String,
+    value: &Value,
+    spec: &Spec,
+    template: &str,
+) -> Result<(), Error> {
+    if !is_number(value) {
+        if spec.radix.is_some() {
+            return Err(get_format_error(
+                &format!("can only format integers in a radix, recieved: {:?}", value),
+                template,
+            ));
+        }
+
+        // like in rust the precision cuts strings short
+        let mut body = value.to_string();
+        if let Some(precision) = spec.precision {
+            body = body.chars().take(precision).collect();
+        }
+
+        pad(output, &body, spec, Align::Left);
+        return Ok(());
+    }
+
+    let (negative, prefix, digits) = format_number(value, spec, template)?;
+    let sign = match (negative, spec.plus) {
+        (true, _) => "-",
+        (false, true) => "+",
+        (false, false) => "",
+    };
+
+    // zeros go between the sign and the digits
+    if spec.zero && spec.align.is_none() {
+        let zeros = spec
+            .width
+            .saturating_sub(sign.len() + prefix.len() + digits.chars().count());
+        output.push_str(sign);
+        output.push_str(&prefix);
+        output.extend(::std::iter::repeat_n('0', zeros));
+        output.push_str(&digits);
+        return Ok(());
+    }
+
+    pad(
+        output,
+        &format!("{}{}{}", sign, prefix, digits),
+        spec,
+        Align::Right,
+    );
+    Ok(())
+}
+
+// marks the argument it takes as used
+fn get_argument(
+    placeholder: Placeholder,
+    next_index: &mut usize,
+    used: &mut [bool],
+    args: &[Value],
+    template: &str,
+) -> Result<Value, Error> {
+    let index = match placeholder {
+        Placeholder::Next => {
+            *next_index += 1;
+            *next_index - 1
+        }
+        Placeholder::Index(index) => index,
+        Placeholder::Name(name) => {
+            if let Some(used) = used.first_mut() {
+                *used = true;
+            }
+
+            return match args.first() {
+                Some(Value::Structure(structure)) => {
+                    match structure.get_member(&name.to_string()) {
+                        Some(value) => Ok(value),
+                        None => Err(get_format_error(
+                            &format!("structure has no member: \"{}\"", name),
+                            template,
+                        )),
+                    }
+                }
+                _ => Err(get_format_error(
+                    &format!(
+                        "named placeholder: \"{}\" needs a structure as the first argument",
+                        name
+                    ),
+                    template,
+                )),
+            };
+        }
+    };
+
+    match args.get(index) {
+        Some(value) => {
+            used[index] = true;
+            Ok(value.clone())
+        }
+        None => Err(get_format_error(
+            &format!("missing argument: {}", index),
+            template,
+        )),
+    }
+}
+
+// every argument must be used by a placeholder, so that a missing
+// placeholder is not silently ignored
+pub fn format_template(template: &str, args: &[Value]) -> Result<String, Error> {
+    let mut output = String::with_capacity(template.len());
+    let mut next_index = 0;
+    let mut used = vec![false; args.len()];
+    let mut rest = template;
+
+    while let Some(i) = rest.find(['{', '}']) {
+        output.push_str(&rest[..i]);
+        let brace = &rest[i..];
+
+        if brace.starts_with("{{") || brace.starts_with("}}") {
+            output.push_str(&brace[..1]);
+            rest = &brace[2..];
+            continue;
+        }
+
+        if brace.starts_with('}') {
+            return Err(get_format_error("unmatched \"}\"", template));
+        }
+
+        let end = match brace.find('}') {
+            Some(end) => end,
+            None => return Err(get_format_error("unclosed \"{\"", template)),
+        };
+
+        let (placeholder, spec) = parse_placeholder(&brace[1..end], template)?;
+        let value = get_argument(placeholder, &mut next_index, &mut used, args, template)?;
+        format_value(&mut output, &value, &spec, template)?;
+
+        rest = &brace[end + 1..];
+    }
+
+    if let Some(index) = used.iter().position(|used| !used) {
+        return Err(get_format_error(
+            &format!("unused argument: {}", index),
+            template,
+        ));
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
@@ -1,7 +1,41 @@
 use crate::prelude::*;
+use crate::std::logic::values_equal;
+use crate::std::maths::get_argument_count_error;
+use crate::std::string::{
+    get_index_error, get_non_string_type_error, get_slice_bounds, string_index_of, string_length,
+    string_reverse, string_slice,
+};
 
 pub fn add_list_lib(run_state: &mut RunState) -> Result<(), Error> {
     run_state.expose_function("list", std_list_list)?;
+    run_state.expose_function("range", std_list_range)?;
+
+    // these also work on strings
+    run_state.expose_function("length", std_list_length)?;
+    run_state.expose_function("slice", std_list_slice)?;
+    run_state.expose_function("reverse", std_list_reverse)?;
+    run_state.expose_function("contains?", std_list_contains)?;
+    run_state.expose_function("index-of", std_list_index_of)?;
+
+    run_state.expose_function("nth", std_list_nth)?;
+    run_state.expose_function("set-nth", std_list_set_nth)?;
+    run_state.expose_function("first", std_list_first)?;
+    run_state.expose_function("rest", std_list_rest)?;
+    run_state.expose_function("last", std_list_last)?;
+    run_state.expose_function("append", std_list_append)?;
+    run_state.expose_function("concat", std_list_concat)?;
+    run_state.expose_function("flatten", std_list_flatten)?;
+    run_state.expose_function("zip", std_list_zip)?;
+
+    // these take the run state so that they can call back into functions,
+    // the function always comes first and can be followed by any iterable
+    run_state.expose_run_state_function("map", std_list_map)?;
+    run_state.expose_run_state_function("filter", std_list_filter)?;
+    run_state.expose_run_state_function("reduce", std_list_reduce)?;
+    run_state.expose_run_state_function("fold", std_list_fold)?;
+    run_state.expose_run_state_function("any?", std_list_any)?;
+    run_state.expose_run_state_function("all?", std_list_all)?;
+    run_state.expose_run_state_function("find", std_list_find)?;
 
     Ok(())
 }
@@ -9,3 +43,444 @@ pub fn add_list_lib(run_state: &mut RunState) -> Result<(), Error> {
 fn std_list_list(args: Vec<Value>) -> Result<Value, Error> {
     Ok(Value::List(args))
 }
+
+fn std_list_range(args: Vec<Value>) -> Result<Value, Error> {
+    let mut bounds = Vec::with_capacity(args.len());
+    for arg in &args {
+        match arg {
+            Value::Integer(integer) => bounds.push(*integer),
+            _ => {
+                return Err(Error::new(
+                    format!("non integer type: {:?} in function \"range\"", arg),
+                    None,
+                ));
+            }
+        }
+    }
+
+    let range = match bounds.as_slice() {
+        [end] => Range::new(0, *end, 1)?,
+        [start, end] => Range::new(*start, *end, 1)?,
+        [start, end, step] => Range::new(*start, *end, *step)?,
+        _ => {
+            return Err(Error::new(
+                "function \"range\" takes one to three arguments".to_string(),
+                None,
+            ));
+        }
+    };
+
+    Ok(Value::Range(range))
+}
+
+fn get_non_list_type_error(function_name: &str, arg: &Value) -> Error {
+    Error::new(
+        format!("non list type: {:?} in function \"{}\"", arg, function_name),
+        None,
+    )
+}
+
+fn get_list<'a>(function_name: &str, arg: &'a Value) -> Result<&'a Vec<Value>, Error> {
+    match arg {
+        Value::List(list) => Ok(list),
+        _ => Err(get_non_list_type_error(function_name, arg)),
+    }
+}
+
+fn get_single_list<'a>(function_name: &str, args: &'a [Value]) -> Result<&'a Vec<Value>, Error> {
+    match args {
+        [arg] => get_list(function_name, arg),
+        _ => Err(get_argument_count_error(function_name, "one argument")),
+    }
+}
+
+pub fn get_elements(function_name: &str, arg: &Value) -> Result<Vec<Value>, Error> {
+    match arg.iter() {
+        Some(iter) => Ok(iter.collect()),
+        None => Err(Error::new(
+            format!(
+                "non iterable type: {:?} in function \"{}\"",
+                arg, function_name
+            ),
+            None,
+        )),
+    }
+}
+
+fn get_index(function_name: &str, arg: &Value, len: usize) -> Result<usize, Error> {
+    match arg {
+        Value::Integer(index) if (0..len as i64).contains(index) => Ok(*index as usize),
+        Value::Integer(index) => Err(get_index_error(function_name, *index, len)),
+        _ => Err(Error::new(
+            format!(
+                "non integer type: {:?} in function \"{}\"",
+                arg, function_name
+            ),
+            None,
+        )),
+    }
+}
+
+// callables are functions defined in scripts or native functions
+pub fn call_value(
+    run_state: &mut RunState,
+    function_name: &str,
+    function: &Value,
+    args: Vec<Value>,
+) -> Result<Value, Error> {
+    match function {
+        Value::Function(function) => function.call(run_state, args),
+        Value::NativeFunction(function) => function(args),
+        Value::NativeRunStateFunction(function) => function(run_state, args),
+        _ => Err(Error::new(
+            format!(
+                "non function type: {:?} in function \"{}\"",
+                function, function_name
+            ),
+            None,
+        )),
+    }
+}
+
+fn call_predicate(
+    run_state: &mut RunState,
+    function_name: &str,
+    function: &Value,
+    arg: Value,
+) -> Result<bool, Error> {
+    match call_value(run_state, function_name, function, vec![arg])? {
+        Value::Boolean(boolean) => Ok(boolean),
+        value => Err(Error::new(
+            format!(
+                "predicate must return a boolean, recieved: {:?} in function \"{}\"",
+                value, function_name
+            ),
+            None,
+        )),
+    }
+}
+
+fn std_list_length(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [Value::List(list)] => Ok(Value::Integer(list.len() as i64)),
+        [Value::String(string)] => Ok(string_length(string)),
+        [Value::Range(range)] => Ok(Value::Integer(range.len() as i64)),
+        [Value::Map(map)] => Ok(Value::Integer(map.len() as i64)),
+        [Value::Set(set)] => Ok(Value::Integer(set.len() as i64)),
+        [Value::Deque(deque)] => Ok(Value::Integer(deque.len() as i64)),
+        [Value::PriorityQueue(queue)] => Ok(Value::Integer(queue.len() as i64)),
+        [Value::SortedMap(map)] => Ok(Value::Integer(map.len() as i64)),
+        [arg] => Err(get_non_list_type_error("length", arg)),
+        _ => Err(get_argument_count_error("length", "one argument")),
+    }
+}
+
+// negative indices count back from the end
+fn std_list_slice(args: Vec<Value>) -> Result<Value, Error> {
+    match args.split_first() {
+        Some((Value::List(list), bounds)) => {
+            let (start, end) = get_slice_bounds("slice", bounds, list.len(), true)?;
+            Ok(Value::List(list[start..end].to_vec()))
+        }
+        Some((Value::String(string), bounds)) => string_slice("slice", string, bounds, true),
+        Some((arg, _)) => Err(get_non_list_type_error("slice", arg)),
+        None => Err(get_argument_count_error(
+            "slice",
+            "a list and a start index",
+        )),
+    }
+}
+
+fn std_list_reverse(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [Value::List(list)] => Ok(Value::List(list.iter().rev().cloned().collect())),
+        [Value::String(string)] => Ok(string_reverse(string)),
+        [arg] => Err(get_non_list_type_error("reverse", arg)),
+        _ => Err(get_argument_count_error("reverse", "one argument")),
+    }
+}
+
+fn std_list_contains(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [Value::List(list), element] => Ok(Value::Boolean(
+            list.iter().any(|other| values_equal(other, element)),
+        )),
+        [Value::String(string), Value::String(pattern)] => {
+            Ok(Value::Boolean(string.contains(pattern.as_str())))
+        }
+        [Value::String(_), pattern] => Err(get_non_string_type_error("contains?", pattern)),
+        [arg, _] => Err(get_non_list_type_error("contains?", arg)),
+        _ => Err(get_argument_count_error("contains?", "two arguments")),
+    }
+}
+
+// gives back null if the element is not found
+fn std_list_index_of(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [Value::List(list), element] => Ok(list
+            .iter()
+            .position(|other| values_equal(other, element))
+            .map_or(Value::Null, |index| Value::Integer(index as i64))),
+        [Value::String(string), Value::String(pattern)] => Ok(string_index_of(string, pattern)),
+        [Value::String(_), pattern] => Err(get_non_string_type_error("index-of", pattern)),
+        [arg, _] => Err(get_non_list_type_error("index-of", arg)),
+        _ => Err(get_argument_count_error("index-of", "two arguments")),
+    }
+}
+
+fn std_list_nth(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [list, index] => {
+            let list = get_list("nth", list)?;
+            Ok(list[get_index("nth", index, list.len())?].clone())
+        }
+        _ => Err(get_argument_count_error("nth", "two arguments")),
+    }
+}
+
+// lists are values so this gives back a changed copy
+fn std_list_set_nth(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [list, index, value] => {
+            let mut list = get_list("set-nth", list)?.clone();
+            let index = get_index("set-nth", index, list.len())?;
+            list[index] = value.clone();
+            Ok(Value::List(list))
+        }
+        _ => Err(get_argument_count_error("set-nth", "three arguments")),
+    }
+}
+
+// first and last give back null for empty lists
+fn std_list_first(args: Vec<Value>) -> Result<Value, Error> {
+    let list = get_single_list("first", &args)?;
+    Ok(list.first().cloned().unwrap_or_default())
+}
+
+fn std_list_rest(args: Vec<Value>) -> Result<Value, Error> {
+    let list = get_single_list("rest", &args)?;
+    Ok(Value::List(list.iter().skip(1).cloned().collect()))
+}
+
+fn std_list_last(args: Vec<Value>) -> Result<Value, Error> {
+    let list = get_single_list("last", &args)?;
+    Ok(list.last().cloned().unwrap_or_default())
+}
+
+fn std_list_append(args: Vec<Value>) -> Result<Value, Error> {
+    match args.split_first() {
+        Some((list, elements)) => {
+            let mut list = get_list("append", list)?.clone();
+            list.extend_from_slice(elements);
+            Ok(Value::List(list))
+        }
+        None => Err(get_argument_count_error(
+            "append",
+            "a list and the elements to add",
+        )),
+    }
+}
+
+fn std_list_concat(args: Vec<Value>) -> Result<Value, Error> {
+    let mut output = Vec::new();
+    for arg in &args {
+        output.extend_from_slice(get_list("concat", arg)?);
+    }
+
+    Ok(Value::List(output))
+}
+
+fn flatten_into(output: &mut Vec<Value>, list: &[Value]) {
+    for element in list {
+        match element {
+            Value::List(inner) => flatten_into(output, inner),
+            _ => output.push(element.clone()),
+        }
+    }
+}
+
+// flattens every level of nesting
+fn std_list_flatten(args: Vec<Value>) -> Result<Value, Error> {
+    let list = get_single_list("flatten", &args)?;
+
+    let mut output = Vec::with_capacity(list.len());
+    flatten_into(&mut output, list);
+    Ok(Value::List(output))
+}
+
+// stops at the end of the shortest list
+fn zip_elements(lists: Vec<Vec<Value>>) -> Vec<Vec<Value>> {
+    let len = lists.iter().map(Vec::len).min().unwrap_or(0);
+    let mut iters: Vec<_> = lists.into_iter().map(Vec::into_iter).collect();
+
+    (0..len)
+        .map(|_| iters.iter_mut().filter_map(Iterator::next).collect())
+        .collect()
+}
+
+fn std_list_zip(args: Vec<Value>) -> Result<Value, Error> {
+    let mut lists = Vec::with_capacity(args.len());
+    for arg in &args {
+        lists.push(get_elements("zip", arg)?);
+    }
+
+    Ok(Value::List(
+        zip_elements(lists).into_iter().map(Value::List).collect(),
+    ))
+}
+
+fn split_function_arg<'a>(
+    function_name: &str,
+    args: &'a [Value],
+    expected: &str,
+) -> Result<(&'a Value, &'a [Value]), Error> {
+    match args.split_first() {
+        Some((function, rest)) if !rest.is_empty() => Ok((function, rest)),
+        _ => Err(get_argument_count_error(function_name, expected)),
+    }
+}
+
+pub fn get_function_and_elements<'a>(
+    function_name: &str,
+    args: &'a [Value],
+) -> Result<(&'a Value, Vec<Value>), Error> {
+    match args {
+        [function, elements] => Ok((function, get_elements(function_name, elements)?)),
+        _ => Err(get_argument_count_error(
+            function_name,
+            "a function and a list",
+        )),
+    }
+}
+
+// with more than one list the function takes an element from each
+fn std_list_map(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let (function, lists) = split_function_arg("map", &args, "a function and a list")?;
+
+    let mut elements = Vec::with_capacity(lists.len());
+    for list in lists {
+        elements.push(get_elements("map", list)?);
+    }
+
+    let mut output = Vec::new();
+    for args in zip_elements(elements) {
+        output.push(call_value(run_state, "map", function, args)?);
+    }
+
+    Ok(Value::List(output))
+}
+
+fn std_list_filter(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let (function, elements) = get_function_and_elements("filter", &args)?;
+
+    let mut output = Vec::new();
+    for element in elements {
+        if call_predicate(run_state, "filter", function, element.clone())? {
+            output.push(element);
+        }
+    }
+
+    Ok(Value::List(output))
+}
+
+fn fold_elements(
+    run_state: &mut RunState,
+    function_name: &str,
+    function: &Value,
+    initial: Value,
+    elements: impl Iterator<Item = Value>,
+) -> Result<Value, Error> {
+    let mut res = initial;
+    for element in elements {
+        res = call_value(run_state, function_name, function, vec![res, element])?;
+    }
+
+    Ok(res)
+}
+
+// "(reduce f list)" starts from the first element, "(reduce f init list)"
+// is the same as fold
+fn std_list_reduce(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [function, elements] => {
+            let mut elements = get_elements("reduce", elements)?.into_iter();
+            match elements.next() {
+                Some(initial) => fold_elements(run_state, "reduce", function, initial, elements),
+                None => Err(Error::new(
+                    "can not reduce an empty list without an initial value".to_string(),
+                    None,
+                )),
+            }
+        }
+        [function, initial, elements] => {
+            let elements = get_elements("reduce", elements)?;
+            fold_elements(
+                run_state,
+                "reduce",
+                function,
+                initial.clone(),
+                elements.into_iter(),
+            )
+        }
+        _ => Err(get_argument_count_error(
+            "reduce",
+            "a function, an optional initial value and a list",
+        )),
+    }
+}
+
+fn std_list_fold(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [function, initial, elements] => {
+            let elements = get_elements("fold", elements)?;
+            fold_elements(
+                run_state,
+                "fold",
+                function,
+                initial.clone(),
+                elements.into_iter(),
+            )
+        }
+        _ => Err(get_argument_count_error(
+            "fold",
+            "a function, an initial value and a list",
+        )),
+    }
+}
+
+fn std_list_any(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let (function, elements) = get_function_and_elements("any?", &args)?;
+
+    for element in elements {
+        if call_predicate(run_state, "any?", function, element)? {
+            return Ok(Value::Boolean(true));
+        }
+    }
+
+    Ok(Value::Boolean(false))
+}
+
+fn std_list_all(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let (function, elements) = get_function_and_elements("all?", &args)?;
+
+    for element in elements {
+        if !call_predicate(run_state, "all?", function, element)? {
+            return Ok(Value::Boolean(false));
+        }
+    }
+
+    Ok(Value::Boolean(true))
+}
+
+// gives back null if nothing matches
+fn std_list_find(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let (function, elements) = get_function_and_elements("find", &args)?;
+
+    for element in elements {
+        if call_predicate(run_state, "find", function, element.clone())? {
+            return Ok(element);
+        }
+    }
+
+    Ok(Value::Null)
+}
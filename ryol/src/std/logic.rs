@@ -0,0 +1,381 @@
+use crate::prelude::*;
+use crate::std::maths::to_rational;
+use ::std::borrow::Borrow;
+use ::std::cmp::Ordering;
+use ::std::rc::Rc;
+use num_rational::BigRational;
+
+pub fn add_logic_lib(run_state: &mut RunState) -> Result<(), Error> {
+    // comparison operators
+    run_state.expose_function("=", std_logic_eq)?;
+    run_state.expose_function("!=", std_logic_ne)?;
+    run_state.expose_function("<", std_logic_lt)?;
+    run_state.expose_function("<=", std_logic_le)?;
+    run_state.expose_function(">", std_logic_gt)?;
+    run_state.expose_function(">=", std_logic_ge)?;
+
+    // aliases for the comparison operators
+    run_state.expose_function("≠", std_logic_ne)?;
+    run_state.expose_function("≤", std_logic_le)?;
+    run_state.expose_function("≥", std_logic_ge)?;
+
+    // logical operators, and/or are macros so that they can short circuit
+    run_state.expose_macro("and", std_logic_and)?;
+    run_state.expose_macro("or", std_logic_or)?;
+    run_state.expose_function("not", std_logic_not)?;
+
+    // "=" compares contents, "identical?" checks for the same instance
+    run_state.expose_function("identical?", std_logic_identical)?;
+
+    Ok(())
+}
+
+// numbers are compared by their exact value, the same as when ordering them
+// and using them as map keys, lists, maps and structures are compared element
+// by element the same way
+pub fn values_equal(x: &Value, y: &Value) -> bool {
+    match (x, y) {
+        (Value::Float(_), _) | (_, Value::Float(_)) => {
+            matches!(compare_numbers(x, y), Some(Some(Ordering::Equal)))
+        }
+        (Value::List(x), Value::List(y)) => {
+            x.len() == y.len() && x.iter().zip(y).all(|(x, y)| values_equal(x, y))
+        }
+        (Value::Map(x), Value::Map(y)) => {
+            x.len() == y.len()
+                && x.iter().all(|(key, x)| match y.get(key) {
+                    Some(y) => values_equal(x, y),
+                    None => false,
+                })
+        }
+        (Value::Structure(x), Value::Structure(y)) => {
+            if x.get_template_identifier() != y.get_template_identifier() {
+                return false;
+            }
+
+            let (x, y) = (x.get_sorted_members(), y.get_sorted_members());
+            x.len() == y.len()
+                && x.iter()
+                    .zip(&y)
+                    .all(|((x_name, x), (y_name, y))| x_name == y_name && values_equal(x, y))
+        }
+        _ => x == y,
+    }
+}
+
+// structures, errors and the shared collections are only identical to copies
+// of the same instance. everything else is a plain value so is identical to
+// anything of the same type that is equal to it
+pub fn values_identical(x: &Value, y: &Value) -> bool {
+    match (x, y) {
+        (Value::Structure(x), Value::Structure(y)) => x.is_identical(y),
+        (Value::Error(x), Value::Error(y)) => Rc::ptr_eq(x, y),
+        (Value::Deque(x), Value::Deque(y)) => x.ptr_eq(y),
+        (Value::PriorityQueue(x), Value::PriorityQueue(y)) => x.ptr_eq(y),
+        (Value::SortedMap(x), Value::SortedMap(y)) => x.ptr_eq(y),
+        _ => x == y,
+    }
+}
+
+// a total order over every value, used for sorting:
+//
+// - values of different types are ordered by type: null, booleans, numbers,
+//   strings, symbols, keywords, lists, ranges, maps, sets, structures,
+//   deques, priority queues, sorted maps, errors and then callables
+// - numbers are compared exactly by value whatever their type, negative
+//   infinity comes first, then infinity and nan comes after every number
+// - strings, symbols and keywords are compared by their characters
+// - lists are compared element by element, a list comes before any longer
+//   list that starts with it
+// - ranges are compared by their start, end and then step
+// - maps are compared by their entries in key order
+// - sets are compared by their elements in order
+// - deques, priority queues and sorted maps are compared by their elements
+//   in the order they are iterated over
+// - structures are compared by their template name and then by their members
+//   in name order
+// - errors and callables have no order between themselves
+pub fn order_values(x: &Value, y: &Value) -> Ordering {
+    match (x, y) {
+        (Value::Integer(x), Value::Integer(y)) => x.cmp(y),
+        (Value::Boolean(x), Value::Boolean(y)) => x.cmp(y),
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Symbol(x), Value::Symbol(y)) | (Value::Keyword(x), Value::Keyword(y)) => {
+            x.get_name().cmp(y.get_name())
+        }
+        (Value::List(x), Value::List(y)) => order_lists(x.iter(), y.iter()),
+        (Value::Range(x), Value::Range(y)) => (x.get_start(), x.get_end(), x.get_step()).cmp(&(
+            y.get_start(),
+            y.get_end(),
+            y.get_step(),
+        )),
+        (Value::Map(x), Value::Map(y)) => order_lists(
+            get_sorted_entries(x).into_iter(),
+            get_sorted_entries(y).into_iter(),
+        ),
+        (Value::Set(x), Value::Set(y)) => order_lists(
+            get_sorted_elements(x).into_iter(),
+            get_sorted_elements(y).into_iter(),
+        ),
+        (Value::Deque(_), Value::Deque(_))
+        | (Value::PriorityQueue(_), Value::PriorityQueue(_))
+        | (Value::SortedMap(_), Value::SortedMap(_)) => match (x.iter(), y.iter()) {
+            (Some(x), Some(y)) => order_lists(x, y),
+            _ => unreachable!(),
+        },
+        (Value::Structure(x), Value::Structure(y)) => x
+            .get_template_identifier()
+            .cmp(y.get_template_identifier())
+            .then_with(|| {
+                let (x, y) = (x.get_sorted_members(), y.get_sorted_members());
+                let x = x
+                    .iter()
+                    .flat_map(|(name, value)| [Value::String(name.to_string()), value.clone()]);
+                let y = y
+                    .iter()
+                    .flat_map(|(name, value)| [Value::String(name.to_string()), value.clone()]);
+                order_lists(x, y)
+            }),
+        _ => match (get_number_key(x), get_number_key(y)) {
+            (Some(x), Some(y)) => x.cmp(&y),
+            _ => get_type_rank(x).cmp(&get_type_rank(y)),
+        },
+    }
+}
+
+fn get_type_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Boolean(_) => 1,
+        Value::Integer(_) | Value::BigInteger(_) | Value::Rational(_) | Value::Float(_) => 2,
+        Value::String(_) => 3,
+        Value::Symbol(_) => 4,
+        Value::Keyword(_) => 5,
+        Value::List(_) => 6,
+        Value::Range(_) => 7,
+        Value::Map(_) => 8,
+        Value::Set(_) => 9,
+        Value::Structure(_) => 10,
+        Value::Deque(_) => 11,
+        Value::PriorityQueue(_) => 12,
+        Value::SortedMap(_) => 13,
+        Value::Error(_) => 14,
+        Value::Function(_)
+        | Value::Macro(_)
+        | Value::NativeFunction(_)
+        | Value::NativeRunStateFunction(_)
+        | Value::NativeMacro(_) => 15,
+    }
+}
+
+// the variant order is the order of the numbers
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum NumberKey {
+    NegativeInfinity,
+    Finite(BigRational),
+    Infinity,
+    NaN,
+}
+
+// floats are turned into rationals exactly so that the order is consistent
+fn get_number_key(value: &Value) -> Option<NumberKey> {
+    match value {
+        Value::Float(float) if float.is_nan() => Some(NumberKey::NaN),
+        Value::Float(float) if *float == f64::INFINITY => Some(NumberKey::Infinity),
+        Value::Float(float) if *float == f64::NEG_INFINITY => Some(NumberKey::NegativeInfinity),
+        Value::Float(float) => BigRational::from_float(*float).map(NumberKey::Finite),
+        _ => to_rational(value).map(NumberKey::Finite),
+    }
+}
+
+// none if either is not a number, and an ordering of none if either is nan.
+// floats are compared by the rational they stand for, so that a float is only
+// equal to a number with exactly its value
+fn compare_numbers(x: &Value, y: &Value) -> Option<Option<Ordering>> {
+    match (get_number_key(x)?, get_number_key(y)?) {
+        (NumberKey::NaN, _) | (_, NumberKey::NaN) => Some(None),
+        (x, y) => Some(Some(x.cmp(&y))),
+    }
+}
+
+fn order_lists<T: Borrow<Value>>(
+    mut x: impl Iterator<Item = T>,
+    mut y: impl Iterator<Item = T>,
+) -> Ordering {
+    loop {
+        match (x.next(), y.next()) {
+            (Some(x), Some(y)) => match order_values(x.borrow(), y.borrow()) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            },
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (None, None) => return Ordering::Equal,
+        }
+    }
+}
+
+fn get_sorted_elements(set: &Set) -> Vec<&Value> {
+    let mut elements: Vec<&Value> = set.iter().collect();
+    elements.sort_by(|x, y| order_values(x, y));
+    elements
+}
+
+fn get_sorted_entries(map: &Map) -> Vec<Value> {
+    let mut entries: Vec<(&Value, &Value)> = map.iter().collect();
+    entries.sort_by(|(x, _), (y, _)| order_values(x, y));
+    entries
+        .into_iter()
+        .flat_map(|(key, value)| [key.clone(), value.clone()])
+        .collect()
+}
+
+// returns none if the values are unordered (which is only the case for nan)
+pub fn compare_values(
+    function_name: &str,
+    x: &Value,
+    y: &Value,
+) -> Result<Option<Ordering>, Error> {
+    match (x, y) {
+        (Value::Integer(x), Value::Integer(y)) => return Ok(Some(x.cmp(y))),
+        (Value::String(x), Value::String(y)) => return Ok(Some(x.cmp(y))),
+        _ => {}
+    }
+
+    if let Some(ordering) = compare_numbers(x, y) {
+        return Ok(ordering);
+    }
+
+    Err(Error::new(
+        format!(
+            "can not compare: {:?} with {:?} in function \"{}\"",
+            x, y, function_name
+        ),
+        None,
+    ))
+}
+
+fn check_arg_count(function_name: &str, args: &[Value]) -> Result<(), Error> {
+    if args.len() < 2 {
+        return Err(Error::new(
+            format!(
+                "function \"{}\" requires at least two arguments",
+                function_name
+            ),
+            None,
+        ));
+    }
+
+    Ok(())
+}
+
+fn std_logic_eq(args: Vec<Value>) -> Result<Value, Error> {
+    check_arg_count("=", &args)?;
+
+    Ok(Value::Boolean(
+        args.windows(2).all(|pair| values_equal(&pair[0], &pair[1])),
+    ))
+}
+
+// true only when no two arguments are equal, so "(!= 1 2 1)" is false even
+// though neighbouring arguments differ
+fn std_logic_ne(args: Vec<Value>) -> Result<Value, Error> {
+    check_arg_count("!=", &args)?;
+
+    for (i, x) in args.iter().enumerate() {
+        if args[i + 1..].iter().any(|y| values_equal(x, y)) {
+            return Ok(Value::Boolean(false));
+        }
+    }
+
+    Ok(Value::Boolean(true))
+}
+
+// checks that every neighbouring pair of arguments is ordered as expected
+fn compare_chain(
+    function_name: &str,
+    args: &[Value],
+    is_ordered: fn(Ordering) -> bool,
+) -> Result<Value, Error> {
+    check_arg_count(function_name, args)?;
+
+    for pair in args.windows(2) {
+        match compare_values(function_name, &pair[0], &pair[1])? {
+            Some(ordering) if is_ordered(ordering) => {}
+            _ => {
+                return Ok(Value::Boolean(false));
+            }
+        }
+    }
+
+    Ok(Value::Boolean(true))
+}
+
+fn std_logic_lt(args: Vec<Value>) -> Result<Value, Error> {
+    compare_chain("<", &args, Ordering::is_lt)
+}
+
+fn std_logic_le(args: Vec<Value>) -> Result<Value, Error> {
+    compare_chain("<=", &args, Ordering::is_le)
+}
+
+fn std_logic_gt(args: Vec<Value>) -> Result<Value, Error> {
+    compare_chain(">", &args, Ordering::is_gt)
+}
+
+fn std_logic_ge(args: Vec<Value>) -> Result<Value, Error> {
+    compare_chain(">=", &args, Ordering::is_ge)
+}
+
+pub fn evaluate_condition(run_state: &mut RunState, node: &Node) -> Result<bool, Error> {
+    match node.evaluate(run_state)? {
+        Value::Boolean(boolean) => Ok(boolean),
+        value => Err(Error::new(
+            format!("condition must be a boolean, recieved: {:?}", value),
+            node.get_token().clone(),
+        )),
+    }
+}
+
+fn std_logic_and(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    for child in node.get_children() {
+        if !evaluate_condition(run_state, child)? {
+            return Ok(Flow::Value(Value::Boolean(false)));
+        }
+    }
+
+    Ok(Flow::Value(Value::Boolean(true)))
+}
+
+fn std_logic_or(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    for child in node.get_children() {
+        if evaluate_condition(run_state, child)? {
+            return Ok(Flow::Value(Value::Boolean(true)));
+        }
+    }
+
+    Ok(Flow::Value(Value::Boolean(false)))
+}
+
+fn std_logic_not(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [Value::Boolean(boolean)] => Ok(Value::Boolean(!boolean)),
+        [arg] => Err(Error::new(
+            format!("non boolean type: {:?} in function \"not\"", arg),
+            None,
+        )),
+        _ => Err(Error::new(
+            "function \"not\" takes one argument".to_string(),
+            None,
+        )),
+    }
+}
+
+fn std_logic_identical(args: Vec<Value>) -> Result<Value, Error> {
+    check_arg_count("identical?", &args)?;
+
+    Ok(Value::Boolean(
+        args.windows(2)
+            .all(|pair| values_identical(&pair[0], &pair[1])),
+    ))
+}
@@ -0,0 +1,133 @@
+use crate::prelude::*;
+use crate::std::basic::define_function;
+
+pub fn add_macros_lib(run_state: &mut RunState) -> Result<(), Error> {
+    run_state.expose_macro("quote", std_macros_quote)?;
+    run_state.expose_macro("quasiquote", std_macros_quasiquote)?;
+    run_state.expose_macro("defmacro", std_macros_defmacro)?;
+    run_state.expose_macro("macroexpand", std_macros_macroexpand)?;
+    run_state.expose_macro("gensym", std_macros_gensym)?;
+
+    Ok(())
+}
+
+fn get_single_arg(node: &Node) -> Result<&Node, Error> {
+    match node.get_children().as_slice() {
+        [arg] => Ok(arg),
+        _ => Err(Error::new(
+            "takes one argument".to_string(),
+            node.get_token().clone(),
+        )),
+    }
+}
+
+fn std_macros_quote(_: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    Ok(Flow::Value(get_single_arg(node)?.to_value()))
+}
+
+// checks for "(unquote x)" and the like
+fn is_form(node: &Node, form_identifier: &str) -> bool {
+    node.is_bracketed()
+        && matches!(get_identifier(node), Ok(identifier) if identifier == form_identifier)
+}
+
+fn quasiquote_node(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
+    if is_form(node, "unquote") {
+        return get_single_arg(node)?.evaluate(run_state);
+    }
+
+    // atoms and empty lists are the same as when quoted
+    if node.get_children().is_empty() {
+        return Ok(node.to_value());
+    }
+
+    let mut list = Vec::with_capacity(node.get_children().len() + 1);
+    if let Some(head) = node.get_head() {
+        list.push(head.to_value());
+    }
+
+    for child in node.get_children() {
+        if is_form(child, "unquote-splicing") {
+            match get_single_arg(child)?.evaluate(run_state)? {
+                Value::List(elements) => list.extend(elements),
+                value => {
+                    return Err(Error::new(
+                        format!("can only splice lists, recieved: {:?}", value),
+                        child.get_token().clone(),
+                    ));
+                }
+            }
+        } else {
+            list.push(quasiquote_node(run_state, child)?);
+        }
+    }
+
+    Ok(Value::List(list))
+}
+
+fn std_macros_quasiquote(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    quasiquote_node(run_state, get_single_arg(node)?).map(Flow::Value)
+}
+
+fn std_macros_defmacro(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    define_function(run_state, node, Value::Macro)
+}
+
+fn std_macros_macroexpand(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    let mut form = get_single_arg(node)?.evaluate(run_state)?;
+
+    // keep expanding until the form no longer starts with a macro
+    loop {
+        let (function, args) = match &form {
+            Value::List(list) => match list.split_first() {
+                Some((Value::Symbol(symbol), args)) => {
+                    match run_state.find_local(&symbol.to_string()) {
+                        Some(Value::Macro(function)) => (function, args.to_vec()),
+                        _ => return Ok(Flow::Value(form)),
+                    }
+                }
+                _ => return Ok(Flow::Value(form)),
+            },
+            _ => return Ok(Flow::Value(form)),
+        };
+
+        form = match function.call(run_state, args) {
+            Ok(expansion) => expansion,
+            Err(mut error) => {
+                if let (None, Some(token)) = (error.get_token(), node.get_token()) {
+                    error.set_token(token.clone());
+                }
+                return Err(error);
+            }
+        };
+    }
+}
+
+fn std_macros_gensym(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    let prefix = match node.get_children().as_slice() {
+        [] => "g".to_string(),
+        [arg] => match arg.evaluate(run_state)? {
+            Value::String(string) => string,
+            Value::Symbol(symbol) => symbol.to_string(),
+            value => {
+                return Err(Error::new(
+                    format!("prefix must be a string, recieved: {:?}", value),
+                    node.get_token().clone(),
+                ));
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                "takes at most one argument".to_string(),
+                node.get_token().clone(),
+            ));
+        }
+    };
+
+    // "#" can appear in identifiers but is unlikely to be typed by hand
+    Ok(Flow::Value(Value::Symbol(Symbol::new(&format!(
+        "{}#{}",
+        prefix,
+        run_state.next_gensym_id()
+    )))))
+}
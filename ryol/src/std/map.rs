@@ -0,0 +1,160 @@
+use crate::prelude::*;
+use crate::std::maths::get_argument_count_error;
+
+pub fn add_map_lib(run_state: &mut RunState) -> Result<(), Error> {
+    // "{k v ...}" is parsed as a call to make-map
+    run_state.expose_function("make-map", std_map_make_map)?;
+    run_state.expose_function("map?", std_map_is_map)?;
+
+    run_state.expose_function("map-get", std_map_get)?;
+    run_state.expose_function("map-set", std_map_set)?;
+    run_state.expose_function("map-remove", std_map_remove)?;
+    run_state.expose_function("map-has?", std_map_has)?;
+    run_state.expose_function("map-keys", std_map_keys)?;
+    run_state.expose_function("map-values", std_map_values)?;
+    run_state.expose_function("map-entries", std_map_entries)?;
+    run_state.expose_function("map-merge", std_map_merge)?;
+    run_state.expose_function("map-length", std_map_length)?;
+
+    Ok(())
+}
+
+pub fn get_non_map_type_error(function_name: &str, arg: &Value) -> Error {
+    Error::new(
+        format!("non map type: {:?} in function \"{}\"", arg, function_name),
+        None,
+    )
+}
+
+// splits off the map that every function other than make-map takes first
+fn split_map_arg(function_name: &str, args: Vec<Value>) -> Result<(Map, Vec<Value>), Error> {
+    let mut args = args.into_iter();
+
+    match args.next() {
+        Some(Value::Map(map)) => Ok((map, args.collect())),
+        Some(arg) => Err(get_non_map_type_error(function_name, &arg)),
+        None => Err(get_argument_count_error(
+            function_name,
+            "a map as its first argument",
+        )),
+    }
+}
+
+fn insert_pairs(function_name: &str, map: &mut Map, args: Vec<Value>) -> Result<(), Error> {
+    if !args.len().is_multiple_of(2) {
+        return Err(get_argument_count_error(function_name, "key/value pairs"));
+    }
+
+    let mut args = args.into_iter();
+    while let (Some(key), Some(value)) = (args.next(), args.next()) {
+        map.insert(key, value)?;
+    }
+
+    Ok(())
+}
+
+fn std_map_make_map(args: Vec<Value>) -> Result<Value, Error> {
+    let mut map = Map::new();
+    insert_pairs("make-map", &mut map, args)?;
+
+    Ok(Value::Map(map))
+}
+
+fn std_map_is_map(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [arg] => Ok(Value::Boolean(matches!(arg, Value::Map(_)))),
+        _ => Err(get_argument_count_error("map?", "one argument")),
+    }
+}
+
+fn std_map_get(args: Vec<Value>) -> Result<Value, Error> {
+    let (map, args) = split_map_arg("map-get", args)?;
+
+    // the optional last argument is given back when the key is missing
+    match args.as_slice() {
+        [key] => Ok(map.get(key).cloned().unwrap_or_default()),
+        [key, default] => Ok(map.get(key).unwrap_or(default).clone()),
+        _ => Err(get_argument_count_error(
+            "map-get",
+            "a map, a key and an optional default",
+        )),
+    }
+}
+
+fn std_map_set(args: Vec<Value>) -> Result<Value, Error> {
+    let (mut map, args) = split_map_arg("map-set", args)?;
+
+    if args.is_empty() {
+        return Err(get_argument_count_error("map-set", "key/value pairs"));
+    }
+    insert_pairs("map-set", &mut map, args)?;
+
+    Ok(Value::Map(map))
+}
+
+fn std_map_remove(args: Vec<Value>) -> Result<Value, Error> {
+    let (mut map, keys) = split_map_arg("map-remove", args)?;
+
+    for key in &keys {
+        map.remove(key);
+    }
+
+    Ok(Value::Map(map))
+}
+
+fn std_map_has(args: Vec<Value>) -> Result<Value, Error> {
+    let (map, args) = split_map_arg("map-has?", args)?;
+
+    match args.as_slice() {
+        [key] => Ok(Value::Boolean(map.contains_key(key))),
+        _ => Err(get_argument_count_error("map-has?", "a map and a key")),
+    }
+}
+
+fn get_only_map_arg(function_name: &str, args: Vec<Value>) -> Result<Map, Error> {
+    let (map, args) = split_map_arg(function_name, args)?;
+
+    if !args.is_empty() {
+        return Err(get_argument_count_error(function_name, "one argument"));
+    }
+
+    Ok(map)
+}
+
+fn std_map_keys(args: Vec<Value>) -> Result<Value, Error> {
+    let map = get_only_map_arg("map-keys", args)?;
+    Ok(Value::List(map.keys().cloned().collect()))
+}
+
+fn std_map_values(args: Vec<Value>) -> Result<Value, Error> {
+    let map = get_only_map_arg("map-values", args)?;
+    Ok(Value::List(map.values().cloned().collect()))
+}
+
+fn std_map_entries(args: Vec<Value>) -> Result<Value, Error> {
+    let map = get_only_map_arg("map-entries", args)?;
+    Ok(Value::List(Value::Map(map).iter().unwrap().collect()))
+}
+
+fn std_map_merge(args: Vec<Value>) -> Result<Value, Error> {
+    let (mut map, others) = split_map_arg("map-merge", args)?;
+
+    // later maps win when keys are in more than one
+    for other in &others {
+        match other {
+            Value::Map(other) => {
+                for (key, value) in other.iter() {
+                    map.insert(key.clone(), value.clone())?;
+                }
+            }
+            _ => return Err(get_non_map_type_error("map-merge", other)),
+        }
+    }
+
+    Ok(Value::Map(map))
+}
+
+fn std_map_length(args: Vec<Value>) -> Result<Value, Error> {
+    let map = get_only_map_arg("map-length", args)?;
+    Ok(Value::Integer(map.len() as i64))
+}
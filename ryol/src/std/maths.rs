@@ -1,5 +1,12 @@
 use crate::error::Error;
 use crate::prelude::*;
+use crate::std::logic::compare_values;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{Pow, Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::f64::consts;
 
 pub fn add_maths_lib(run_state: &mut RunState) -> Result<(), Error> {
     // main operators
@@ -12,6 +19,43 @@ pub fn add_maths_lib(run_state: &mut RunState) -> Result<(), Error> {
     run_state.expose_function("×", std_maths_mlt)?;
     run_state.expose_function("÷", std_maths_div)?;
 
+    // integer division and remainders
+    run_state.expose_function("div", std_maths_div_floor)?;
+    run_state.expose_function("mod", std_maths_mod)?;
+    run_state.expose_function("rem", std_maths_rem)?;
+
+    // powers and logarithms
+    run_state.expose_function("pow", std_maths_pow)?;
+    run_state.expose_function("sqrt", std_maths_sqrt)?;
+    run_state.expose_function("exp", std_maths_exp)?;
+    run_state.expose_function("ln", std_maths_ln)?;
+    run_state.expose_function("log", std_maths_log)?;
+
+    // trigonometry, in radians
+    run_state.expose_function("sin", std_maths_sin)?;
+    run_state.expose_function("cos", std_maths_cos)?;
+    run_state.expose_function("tan", std_maths_tan)?;
+    run_state.expose_function("asin", std_maths_asin)?;
+    run_state.expose_function("acos", std_maths_acos)?;
+    run_state.expose_function("atan", std_maths_atan)?;
+
+    // rounding, which always gives back an integer
+    run_state.expose_function("abs", std_maths_abs)?;
+    run_state.expose_function("floor", std_maths_floor)?;
+    run_state.expose_function("ceil", std_maths_ceil)?;
+    run_state.expose_function("round", std_maths_round)?;
+    run_state.expose_function("trunc", std_maths_trunc)?;
+
+    run_state.expose_function("min", std_maths_min)?;
+    run_state.expose_function("max", std_maths_max)?;
+    run_state.expose_function("clamp", std_maths_clamp)?;
+
+    // constants, which can still be shadowed by locals such as "(catch e ...)"
+    run_state.expose_const("pi", Value::Float(consts::PI))?;
+    run_state.expose_const("tau", Value::Float(consts::TAU))?;
+    run_state.expose_const("e", Value::Float(consts::E))?;
+    run_state.expose_const("euler", Value::Float(consts::E))?;
+
     Ok(())
 }
 
@@ -25,116 +69,567 @@ pub fn get_non_num_type_error(function_name: &str, arg: &Value) -> Error {
     )
 }
 
-fn std_maths_add(args: Vec<Value>) -> Result<Value, Error> {
-    match args.len() {
-        0 => Ok(Value::default()),
-        1 => Ok(args.first().unwrap().clone()),
-        _ => {
-            let mut sum = Value::Integer(0);
-
-            for arg in &args {
-                // i love rust so much for this
-                sum = match (sum, arg) {
-                    (Value::Integer(x), Value::Integer(y)) => Value::Integer(x + y),
-                    (Value::Integer(x), Value::Float(y)) => Value::Integer(x + *y as i64),
-                    (Value::Float(x), Value::Integer(y)) => Value::Float(x + *y as f64),
-                    (Value::Float(x), Value::Float(y)) => Value::Float(x + y),
-                    _ => {
-                        return Err(get_non_num_type_error("+", arg));
-                    }
-                };
-            }
+pub fn get_argument_count_error(function_name: &str, expected: &str) -> Error {
+    Error::new(
+        format!("function \"{}\" takes {}", function_name, expected),
+        None,
+    )
+}
+
+pub fn get_division_by_zero_error(function_name: &str) -> Error {
+    Error::new(
+        format!("division by zero in function \"{}\"", function_name),
+        None,
+    )
+}
+
+// big integers are only used for values that do not fit into an i64, so that
+// there is only one way to represent each number
+pub fn from_big_integer(big_integer: BigInt) -> Value {
+    match big_integer.to_i64() {
+        Some(integer) => Value::Integer(integer),
+        None => Value::BigInteger(big_integer),
+    }
+}
+
+pub fn to_big_integer(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Integer(integer) => Some(BigInt::from(*integer)),
+        Value::BigInteger(big_integer) => Some(big_integer.clone()),
+        _ => None,
+    }
+}
+
+// rationals are only used for values that are not whole numbers
+pub fn from_rational(rational: BigRational) -> Value {
+    if rational.is_integer() {
+        from_big_integer(rational.to_integer())
+    } else {
+        Value::Rational(rational)
+    }
+}
+
+pub fn to_rational(value: &Value) -> Option<BigRational> {
+    match value {
+        Value::Rational(rational) => Some(rational.clone()),
+        _ => Some(BigRational::from_integer(to_big_integer(value)?)),
+    }
+}
+
+pub fn to_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(integer) => Some(*integer as f64),
+        Value::BigInteger(big_integer) => big_integer.to_f64(),
+        Value::Rational(rational) => rational.to_f64(),
+        Value::Float(float) => Some(*float),
+        _ => None,
+    }
+}
+
+struct Operator {
+    name: &'static str,
+    // gives back none on overflow, which then uses big_integer instead
+    integer: fn(i64, i64) -> Option<i64>,
+    big_integer: fn(BigInt, BigInt) -> BigInt,
+    rational: fn(BigRational, BigRational) -> BigRational,
+    float: fn(f64, f64) -> f64,
+}
+
+const ADD: Operator = Operator {
+    name: "+",
+    integer: i64::checked_add,
+    big_integer: |x, y| x + y,
+    rational: |x, y| x + y,
+    float: |x, y| x + y,
+};
+
+const SUB: Operator = Operator {
+    name: "-",
+    integer: i64::checked_sub,
+    big_integer: |x, y| x - y,
+    rational: |x, y| x - y,
+    float: |x, y| x - y,
+};
+
+const MLT: Operator = Operator {
+    name: "*",
+    integer: i64::checked_mul,
+    big_integer: |x, y| x * y,
+    rational: |x, y| x * y,
+    float: |x, y| x * y,
+};
+
+const DIV: Operator = Operator {
+    name: "/",
+    integer: i64::checked_div,
+    big_integer: |x, y| x / y,
+    rational: |x, y| x / y,
+    float: |x, y| x / y,
+};
+
+// integers stay as integers (growing into big integers when they overflow),
+// anything involving a rational is exact unless it also involves a float, and
+// anything involving a float becomes a float
+fn apply_operator(operator: &Operator, x: &Value, y: &Value) -> Result<Value, Error> {
+    if let (Value::Integer(x), Value::Integer(y)) = (x, y) {
+        if let Some(res) = (operator.integer)(*x, *y) {
+            return Ok(Value::Integer(res));
+        }
+    }
+
+    if matches!(x, Value::Rational(_)) || matches!(y, Value::Rational(_)) {
+        if let (Some(x), Some(y)) = (to_rational(x), to_rational(y)) {
+            return Ok(from_rational((operator.rational)(x, y)));
+        }
+    }
+
+    if let (Some(x), Some(y)) = (to_big_integer(x), to_big_integer(y)) {
+        return Ok(from_big_integer((operator.big_integer)(x, y)));
+    }
+
+    match (to_float(x), to_float(y)) {
+        (Some(x), Some(y)) => Ok(Value::Float((operator.float)(x, y))),
+        (Some(_), None) => Err(get_non_num_type_error(operator.name, y)),
+        _ => Err(get_non_num_type_error(operator.name, x)),
+    }
+}
+
+// big integers are never zero as zero fits into an i64
+fn is_zero(value: &Value) -> bool {
+    match value {
+        Value::Integer(integer) => *integer == 0,
+        Value::Float(float) => *float == 0.0,
+        _ => false,
+    }
+}
+
+fn check_number(function_name: &str, arg: &Value) -> Result<(), Error> {
+    match to_float(arg) {
+        Some(_) => Ok(()),
+        None => Err(get_non_num_type_error(function_name, arg)),
+    }
+}
+
+fn fold_args(operator: &Operator, first: &Value, rest: &[Value]) -> Result<Value, Error> {
+    let mut res = first.clone();
+
+    for arg in rest {
+        res = apply_operator(operator, &res, arg)?;
+    }
+
+    Ok(res)
+}
 
-            Ok(sum)
+fn std_maths_add(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [] => Ok(Value::default()),
+        [arg] => {
+            check_number("+", arg)?;
+            Ok(arg.clone())
         }
+        [first, rest @ ..] => fold_args(&ADD, first, rest),
     }
 }
 
 fn std_maths_sub(args: Vec<Value>) -> Result<Value, Error> {
-    match args.len() {
-        0 => Ok(Value::default()),
-        1 => {
-            let arg = args.first().unwrap();
-            match &arg {
-                Value::Integer(x) => Ok(Value::Integer(-x)),
-                Value::Float(x) => Ok(Value::Float(-x)),
-                _ => Err(get_non_num_type_error("-", arg)),
-            }
+    match args.as_slice() {
+        [] => Ok(Value::default()),
+        // "(- x)" is the same as "(- 0 x)"
+        [arg] => apply_operator(&SUB, &Value::Integer(0), arg),
+        [first, rest @ ..] => fold_args(&SUB, first, rest),
+    }
+}
+
+fn std_maths_mlt(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [] => Ok(Value::default()),
+        [arg] => {
+            check_number("*", arg)?;
+            Ok(arg.clone())
         }
-        _ => {
-            let mut sum = args.first().unwrap().clone();
-
-            for arg in &args[1..] {
-                // i love rust so much for this
-                sum = match (sum, arg) {
-                    (Value::Integer(x), Value::Integer(y)) => Value::Integer(x - y),
-                    (Value::Integer(x), Value::Float(y)) => Value::Integer(x - *y as i64),
-                    (Value::Float(x), Value::Integer(y)) => Value::Float(x - *y as f64),
-                    (Value::Float(x), Value::Float(y)) => Value::Float(x - y),
-                    _ => {
-                        return Err(get_non_num_type_error("-", arg));
-                    }
-                };
+        [first, rest @ ..] => fold_args(&MLT, first, rest),
+    }
+}
+
+fn apply_division(operator: &Operator, x: &Value, y: &Value) -> Result<Value, Error> {
+    // checked before the operator as floats would otherwise give back infinity
+    if is_zero(y) {
+        return Err(get_division_by_zero_error(operator.name));
+    }
+
+    apply_operator(operator, x, y)
+}
+
+fn std_maths_div(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [] => Ok(Value::default()),
+        // "(/ x)" is the reciprocal, the same as "(/ 1 x)"
+        [arg] => apply_division(&DIV, &Value::Integer(1), arg),
+        [first, rest @ ..] => {
+            let mut res = first.clone();
+
+            for arg in rest {
+                res = apply_division(&DIV, &res, arg)?;
             }
 
-            Ok(sum)
+            Ok(res)
         }
     }
 }
 
-fn std_maths_mlt(args: Vec<Value>) -> Result<Value, Error> {
-    match args.len() {
-        0 => Ok(Value::default()),
-        1 => Ok(args.first().unwrap().clone()),
-        _ => {
-            let mut sum = Value::Integer(1);
-
-            for arg in &args {
-                // i love rust so much for this
-                sum = match (sum, arg) {
-                    (Value::Integer(x), Value::Integer(y)) => Value::Integer(x * y),
-                    (Value::Integer(x), Value::Float(y)) => Value::Integer(x * *y as i64),
-                    (Value::Float(x), Value::Integer(y)) => Value::Float(x * *y as f64),
-                    (Value::Float(x), Value::Float(y)) => Value::Float(x * y),
-                    _ => {
-                        return Err(get_non_num_type_error("*", arg));
-                    }
-                };
-            }
+fn get_domain_error(function_name: &str, arg: f64) -> Error {
+    Error::new(
+        format!(
+            "argument: {} is outside of the domain of function \"{}\"",
+            arg, function_name
+        ),
+        None,
+    )
+}
+
+fn get_single_arg<'a>(function_name: &str, args: &'a [Value]) -> Result<&'a Value, Error> {
+    match args {
+        [arg] => Ok(arg),
+        _ => Err(get_argument_count_error(function_name, "one argument")),
+    }
+}
+
+fn get_float_arg(function_name: &str, arg: &Value) -> Result<f64, Error> {
+    to_float(arg).ok_or_else(|| get_non_num_type_error(function_name, arg))
+}
+
+// the remainder has the same sign as the divisor, unlike "%" in rust
+fn floored_remainder(remainder: i64, y: i64) -> i64 {
+    if remainder != 0 && (remainder < 0) != (y < 0) {
+        remainder + y
+    } else {
+        remainder
+    }
+}
+
+const DIV_FLOOR: Operator = Operator {
+    name: "div",
+    integer: |x, y| {
+        let quotient = x.checked_div(y)?;
+        Some(if floored_remainder(x % y, y) != x % y {
+            quotient - 1
+        } else {
+            quotient
+        })
+    },
+    big_integer: |x, y| x.div_floor(&y),
+    rational: |x, y| (x / y).floor(),
+    float: |x, y| (x / y).floor(),
+};
 
-            Ok(sum)
+const MOD: Operator = Operator {
+    name: "mod",
+    integer: |x, y| Some(floored_remainder(x.checked_rem(y)?, y)),
+    big_integer: |x, y| x.mod_floor(&y),
+    rational: |x, y| x.clone() - y.clone() * (x / y).floor(),
+    float: |x, y| {
+        let remainder = x % y;
+        if remainder != 0.0 && (remainder < 0.0) != (y < 0.0) {
+            remainder + y
+        } else {
+            remainder
         }
+    },
+};
+
+const REM: Operator = Operator {
+    name: "rem",
+    integer: i64::checked_rem,
+    big_integer: |x, y| x % y,
+    rational: |x, y| x.clone() - y.clone() * (x / y).trunc(),
+    float: |x, y| x % y,
+};
+
+fn apply_division_pair(operator: &Operator, args: &[Value]) -> Result<Value, Error> {
+    match args {
+        [x, y] => apply_division(operator, x, y),
+        _ => Err(get_argument_count_error(operator.name, "two arguments")),
     }
 }
 
-fn std_maths_div(args: Vec<Value>) -> Result<Value, Error> {
-    match args.len() {
-        0 => Ok(Value::default()),
-        1 => {
-            let arg = args.first().unwrap();
-            match &arg {
-                Value::Integer(x) => Ok(Value::Integer(-x)),
-                Value::Float(x) => Ok(Value::Float(-x)),
-                _ => Err(get_non_num_type_error("-", arg)),
+fn std_maths_div_floor(args: Vec<Value>) -> Result<Value, Error> {
+    apply_division_pair(&DIV_FLOOR, &args)
+}
+
+fn std_maths_mod(args: Vec<Value>) -> Result<Value, Error> {
+    apply_division_pair(&MOD, &args)
+}
+
+fn std_maths_rem(args: Vec<Value>) -> Result<Value, Error> {
+    apply_division_pair(&REM, &args)
+}
+
+// the largest exact result of pow in bits, larger results would take too
+// long to work out and use too much memory
+const MAX_POW_BITS: u64 = 1 << 20;
+
+// integer exponents keep integers and rationals exact
+fn exact_pow(base: &Value, exponent: i64) -> Result<Value, Error> {
+    let magnitude = match u32::try_from(exponent.unsigned_abs()) {
+        Ok(magnitude) => magnitude,
+        Err(_) => {
+            return Err(Error::new(
+                format!("exponent: {} is too large in function \"pow\"", exponent),
+                None,
+            ));
+        }
+    };
+
+    if let (Value::Integer(base), true) = (base, exponent >= 0) {
+        if let Some(res) = base.checked_pow(magnitude) {
+            return Ok(Value::Integer(res));
+        }
+    }
+
+    let base = to_rational(base).unwrap();
+
+    // each power of the base adds at least this many bits, so this is close
+    // to the size of the result without working it out
+    let bits = (base.numer().bits().saturating_sub(1) + base.denom().bits().saturating_sub(1))
+        .saturating_mul(magnitude.into());
+    if bits > MAX_POW_BITS {
+        return Err(Error::new(
+            format!(
+                "result of {} to the power of {} is too large in function \"pow\"",
+                base, exponent
+            ),
+            None,
+        ));
+    }
+
+    if exponent < 0 {
+        if base.is_zero() {
+            return Err(get_division_by_zero_error("pow"));
+        }
+
+        return Ok(from_rational(base.recip().pow(magnitude)));
+    }
+
+    Ok(from_rational(base.pow(magnitude)))
+}
+
+fn std_maths_pow(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [base, Value::Integer(exponent)] if to_rational(base).is_some() => {
+            exact_pow(base, *exponent)
+        }
+        [base, exponent] => {
+            let (base, exponent) = (get_float_arg("pow", base)?, get_float_arg("pow", exponent)?);
+
+            // a negative base only has a real power when the exponent is whole
+            if base < 0.0 && exponent.is_finite() && exponent.fract() != 0.0 {
+                return Err(get_domain_error("pow", base));
             }
+
+            Ok(Value::Float(base.powf(exponent)))
         }
-        _ => {
-            let mut sum = args.first().unwrap().clone();
-
-            for arg in &args[1..] {
-                // i love rust so much for this
-                sum = match (sum, arg) {
-                    (Value::Integer(x), Value::Integer(y)) => Value::Integer(x / y),
-                    (Value::Integer(x), Value::Float(y)) => Value::Integer(x / *y as i64),
-                    (Value::Float(x), Value::Integer(y)) => Value::Float(x / *y as f64),
-                    (Value::Float(x), Value::Float(y)) => Value::Float(x / y),
-                    _ => {
-                        return Err(get_non_num_type_error("/", arg));
-                    }
-                };
+        _ => Err(get_argument_count_error("pow", "two arguments")),
+    }
+}
+
+// functions that always give back a float, is_in_domain stops them from
+// quietly giving back nan
+fn apply_float_function(
+    function_name: &str,
+    args: &[Value],
+    function: fn(f64) -> f64,
+    is_in_domain: fn(f64) -> bool,
+) -> Result<Value, Error> {
+    let x = get_float_arg(function_name, get_single_arg(function_name, args)?)?;
+
+    if !x.is_nan() && !is_in_domain(x) {
+        return Err(get_domain_error(function_name, x));
+    }
+
+    Ok(Value::Float(function(x)))
+}
+
+fn std_maths_sqrt(args: Vec<Value>) -> Result<Value, Error> {
+    apply_float_function("sqrt", &args, f64::sqrt, |x| x >= 0.0)
+}
+
+fn std_maths_exp(args: Vec<Value>) -> Result<Value, Error> {
+    apply_float_function("exp", &args, f64::exp, |_| true)
+}
+
+fn std_maths_ln(args: Vec<Value>) -> Result<Value, Error> {
+    apply_float_function("ln", &args, f64::ln, |x| x > 0.0)
+}
+
+// "(log x)" is base 10, "(log x base)" is any other base
+fn std_maths_log(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [_] => apply_float_function("log", &args, f64::log10, |x| x > 0.0),
+        [x, base] => {
+            let x = get_float_arg("log", x)?;
+            let base = get_float_arg("log", base)?;
+
+            for arg in [x, base] {
+                if arg <= 0.0 {
+                    return Err(get_domain_error("log", arg));
+                }
+            }
+            if base == 1.0 {
+                return Err(get_domain_error("log", base));
             }
 
-            Ok(sum)
+            Ok(Value::Float(x.log(base)))
         }
+        _ => Err(get_argument_count_error("log", "one or two arguments")),
+    }
+}
+
+fn std_maths_sin(args: Vec<Value>) -> Result<Value, Error> {
+    apply_float_function("sin", &args, f64::sin, |_| true)
+}
+
+fn std_maths_cos(args: Vec<Value>) -> Result<Value, Error> {
+    apply_float_function("cos", &args, f64::cos, |_| true)
+}
+
+fn std_maths_tan(args: Vec<Value>) -> Result<Value, Error> {
+    apply_float_function("tan", &args, f64::tan, |_| true)
+}
+
+fn std_maths_asin(args: Vec<Value>) -> Result<Value, Error> {
+    apply_float_function("asin", &args, f64::asin, |x| x.abs() <= 1.0)
+}
+
+fn std_maths_acos(args: Vec<Value>) -> Result<Value, Error> {
+    apply_float_function("acos", &args, f64::acos, |x| x.abs() <= 1.0)
+}
+
+// "(atan y x)" takes the quadrant into account like atan2
+fn std_maths_atan(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [_] => apply_float_function("atan", &args, f64::atan, |_| true),
+        [y, x] => Ok(Value::Float(
+            get_float_arg("atan", y)?.atan2(get_float_arg("atan", x)?),
+        )),
+        _ => Err(get_argument_count_error("atan", "one or two arguments")),
+    }
+}
+
+fn std_maths_abs(args: Vec<Value>) -> Result<Value, Error> {
+    let arg = get_single_arg("abs", &args)?;
+
+    match arg {
+        Value::Integer(integer) => match integer.checked_abs() {
+            Some(res) => Ok(Value::Integer(res)),
+            None => Ok(from_big_integer(BigInt::from(*integer).abs())),
+        },
+        Value::BigInteger(big_integer) => Ok(Value::BigInteger(big_integer.abs())),
+        Value::Rational(rational) => Ok(Value::Rational(rational.abs())),
+        Value::Float(float) => Ok(Value::Float(float.abs())),
+        _ => Err(get_non_num_type_error("abs", arg)),
+    }
+}
+
+fn apply_rounding(
+    function_name: &str,
+    args: &[Value],
+    float_rounding: fn(f64) -> f64,
+    rational_rounding: fn(&BigRational) -> BigRational,
+) -> Result<Value, Error> {
+    let arg = get_single_arg(function_name, args)?;
+
+    let rational = match arg {
+        Value::Integer(_) | Value::BigInteger(_) => return Ok(arg.clone()),
+        Value::Rational(rational) => rational_rounding(rational),
+        Value::Float(float) => match BigRational::from_float(float_rounding(*float)) {
+            Some(rational) => rational,
+            None => return Err(get_domain_error(function_name, *float)),
+        },
+        _ => return Err(get_non_num_type_error(function_name, arg)),
+    };
+
+    Ok(from_rational(rational))
+}
+
+fn std_maths_floor(args: Vec<Value>) -> Result<Value, Error> {
+    apply_rounding("floor", &args, f64::floor, BigRational::floor)
+}
+
+fn std_maths_ceil(args: Vec<Value>) -> Result<Value, Error> {
+    apply_rounding("ceil", &args, f64::ceil, BigRational::ceil)
+}
+
+// halves are rounded away from zero
+fn std_maths_round(args: Vec<Value>) -> Result<Value, Error> {
+    apply_rounding("round", &args, f64::round, BigRational::round)
+}
+
+fn std_maths_trunc(args: Vec<Value>) -> Result<Value, Error> {
+    apply_rounding("trunc", &args, f64::trunc, BigRational::trunc)
+}
+
+// gives back the first argument that nothing else is further than
+fn find_extreme(function_name: &str, args: &[Value], further: Ordering) -> Result<Value, Error> {
+    let (mut extreme, rest) = match args.split_first() {
+        Some(parts) => parts,
+        None => {
+            return Err(get_argument_count_error(
+                function_name,
+                "at least one argument",
+            ));
+        }
+    };
+
+    check_number(function_name, extreme)?;
+    for arg in rest {
+        check_number(function_name, arg)?;
+
+        if compare_values(function_name, arg, extreme)? == Some(further) {
+            extreme = arg;
+        }
+    }
+
+    Ok(extreme.clone())
+}
+
+fn std_maths_min(args: Vec<Value>) -> Result<Value, Error> {
+    find_extreme("min", &args, Ordering::Less)
+}
+
+fn std_maths_max(args: Vec<Value>) -> Result<Value, Error> {
+    find_extreme("max", &args, Ordering::Greater)
+}
+
+fn std_maths_clamp(args: Vec<Value>) -> Result<Value, Error> {
+    let (x, low, high) = match args.as_slice() {
+        [x, low, high] => (x, low, high),
+        _ => {
+            return Err(get_argument_count_error(
+                "clamp",
+                "a value, a lower bound and an upper bound",
+            ));
+        }
+    };
+
+    for arg in [x, low, high] {
+        check_number("clamp", arg)?;
+    }
+
+    if compare_values("clamp", low, high)? == Some(Ordering::Greater) {
+        return Err(Error::new(
+            format!(
+                "lower bound: {} is greater than upper bound: {} in function \"clamp\"",
+                low, high
+            ),
+            None,
+        ));
+    }
+
+    if compare_values("clamp", x, low)? == Some(Ordering::Less) {
+        Ok(low.clone())
+    } else if compare_values("clamp", x, high)? == Some(Ordering::Greater) {
+        Ok(high.clone())
+    } else {
+        Ok(x.clone())
     }
 }
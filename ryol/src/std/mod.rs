@@ -1,9 +1,22 @@
 pub mod basic;
+pub mod bits;
+pub mod collections;
+pub mod format;
 pub mod list;
+pub mod logic;
+pub mod macros;
+pub mod map;
 pub mod maths;
+pub mod pattern;
 pub mod print;
+pub mod random;
+pub mod rational;
+pub mod regex;
+pub mod set;
+pub mod sort;
 pub mod string;
 pub mod structure;
+pub mod symbol;
 pub mod throw;
 
 use crate::prelude::*;
@@ -11,11 +24,23 @@ use crate::prelude::*;
 pub fn add_std_lib(run_state: &mut RunState) -> Result<(), Error> {
     basic::add_basic_lib(run_state)?;
     list::add_list_lib(run_state)?;
+    sort::add_sort_lib(run_state)?;
+    map::add_map_lib(run_state)?;
+    set::add_set_lib(run_state)?;
+    collections::add_collections_lib(run_state)?;
     maths::add_maths_lib(run_state)?;
+    rational::add_rational_lib(run_state)?;
+    bits::add_bits_lib(run_state)?;
+    logic::add_logic_lib(run_state)?;
+    macros::add_macros_lib(run_state)?;
+    pattern::add_pattern_lib(run_state)?;
     print::add_print_lib(run_state)?;
+    random::add_random_lib(run_state)?;
     throw::add_throw_lib(run_state)?;
     string::add_string_lib(run_state)?;
+    regex::add_regex_lib(run_state)?;
     structure::add_structure_lib(run_state)?;
+    symbol::add_symbol_lib(run_state)?;
 
     Ok(())
 }
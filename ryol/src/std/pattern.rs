@@ -0,0 +1,270 @@
+use crate::prelude::*;
+use crate::std::basic::evaluate_block;
+use crate::std::logic::{evaluate_condition, values_equal};
+
+pub fn add_pattern_lib(run_state: &mut RunState) -> Result<(), Error> {
+    run_state.expose_macro("match", std_pattern_match)?;
+
+    Ok(())
+}
+
+type Bindings = Vec<(String, Value)>;
+
+fn is_keyword(node: &Node, keyword: &str) -> bool {
+    !node.is_bracketed()
+        && node.get_children().is_empty()
+        && matches!(get_identifier(node), Ok(identifier) if identifier == keyword)
+}
+
+fn match_list_pattern(
+    run_state: &RunState,
+    patterns: &[Node],
+    value: &Value,
+    bindings: &mut Bindings,
+) -> Result<bool, Error> {
+    let list = match value {
+        Value::List(list) => list,
+        _ => return Ok(false),
+    };
+
+    // "(list a b & rest)" matches any list with at least two elements
+    let (patterns, rest_pattern) = match patterns.iter().position(|p| is_keyword(p, "&")) {
+        Some(i) if i + 2 == patterns.len() => (&patterns[..i], Some(&patterns[i + 1])),
+        Some(i) => {
+            return Err(Error::new(
+                "\"&\" must be followed by exactly one pattern".to_string(),
+                patterns[i].get_token().clone(),
+            ));
+        }
+        None => (patterns, None),
+    };
+
+    let length_matches = match rest_pattern {
+        Some(_) => list.len() >= patterns.len(),
+        None => list.len() == patterns.len(),
+    };
+    if !length_matches {
+        return Ok(false);
+    }
+
+    for (pattern, element) in patterns.iter().zip(list) {
+        if !match_pattern(run_state, pattern, element, bindings)? {
+            return Ok(false);
+        }
+    }
+
+    match rest_pattern {
+        Some(rest_pattern) => {
+            let rest = Value::List(list[patterns.len()..].to_vec());
+            match_pattern(run_state, rest_pattern, &rest, bindings)
+        }
+        None => Ok(true),
+    }
+}
+
+fn match_structure_pattern(
+    structure_template: &StructureTemplate,
+    run_state: &RunState,
+    member_patterns: &[Node],
+    value: &Value,
+    bindings: &mut Bindings,
+) -> Result<bool, Error> {
+    let structure = match value {
+        Value::Structure(structure)
+            if structure.get_template_identifier() == structure_template.get_identifier() =>
+        {
+            structure
+        }
+        _ => return Ok(false),
+    };
+
+    // "(vec2d x (y 0))" binds the member x to x and checks that y is 0
+    for member_pattern in member_patterns {
+        let member_identifier = get_identifier(member_pattern)?;
+
+        if !structure_template.get_members().contains(member_identifier) {
+            return Err(Error::new(
+                format!(
+                    "structure: \"{}\" does not have member: \"{}\"",
+                    structure_template.get_identifier(),
+                    member_identifier
+                ),
+                member_pattern.get_token().clone(),
+            ));
+        }
+
+        let member = structure.get_member(member_identifier).unwrap_or_default();
+
+        match member_pattern.get_children().as_slice() {
+            [] => bindings.push((member_identifier.clone(), member)),
+            [pattern] => {
+                if !match_pattern(run_state, pattern, &member, bindings)? {
+                    return Ok(false);
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    "member patterns take one pattern".to_string(),
+                    member_pattern.get_token().clone(),
+                ));
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+fn match_pattern(
+    run_state: &RunState,
+    pattern: &Node,
+    value: &Value,
+    bindings: &mut Bindings,
+) -> Result<bool, Error> {
+    let token = match pattern.get_token() {
+        Some(token) => token,
+        None => {
+            return Err(Error::new(
+                "pattern must start with an identifier".to_string(),
+                pattern
+                    .get_children()
+                    .first()
+                    .and_then(|child| child.get_token().clone()),
+            ));
+        }
+    };
+
+    let identifier = match token.get_token_type() {
+        TokenType::Identifier(identifier) => identifier,
+        TokenType::Integer(integer) => return Ok(values_equal(&Value::Integer(*integer), value)),
+        TokenType::Float(float) => return Ok(values_equal(&Value::Float(*float), value)),
+        TokenType::BigInteger(big_integer) => {
+            return Ok(values_equal(&Value::BigInteger(big_integer.clone()), value))
+        }
+        TokenType::Rational(rational) => {
+            return Ok(values_equal(&Value::Rational(rational.clone()), value))
+        }
+        TokenType::String(string) => return Ok(*value == Value::String(string.clone())),
+        TokenType::Keyword(keyword) => return Ok(*value == Value::Keyword(Symbol::new(keyword))),
+        TokenType::LBracket | TokenType::RBracket | TokenType::LBrace | TokenType::RBrace => {
+            unreachable!()
+        }
+    };
+
+    if !pattern.is_bracketed() && pattern.get_children().is_empty() {
+        return match identifier.as_str() {
+            "_" => Ok(true),
+            "true" => Ok(*value == Value::Boolean(true)),
+            "false" => Ok(*value == Value::Boolean(false)),
+            _ => {
+                bindings.push((identifier.clone(), value.clone()));
+                Ok(true)
+            }
+        };
+    }
+
+    let args = pattern.get_children();
+    match identifier.as_str() {
+        "list" => match_list_pattern(run_state, args, value, bindings),
+        "quote" => match args.as_slice() {
+            [arg] => Ok(values_equal(&arg.to_value(), value)),
+            _ => Err(Error::new(
+                "quote pattern takes one argument".to_string(),
+                Some(token.clone()),
+            )),
+        },
+        _ => match run_state.find_structure_template(identifier) {
+            Some(structure_template) => {
+                match_structure_pattern(structure_template, run_state, args, value, bindings)
+            }
+            None => Err(Error::new(
+                format!("unknown pattern: \"{}\"", identifier),
+                Some(token.clone()),
+            )),
+        },
+    }
+}
+
+fn evaluate_clause(
+    run_state: &mut RunState,
+    clause: &Node,
+    value: &Value,
+) -> Result<Option<Flow>, Error> {
+    // "(pattern when guard body...)", a pattern that is a single token is
+    // parsed as the head of the clause
+    let head = clause.get_head();
+    let elements: Vec<&Node> = head.iter().chain(clause.get_children()).collect();
+
+    let (pattern, rest) = match elements.split_first() {
+        Some(parts) => parts,
+        None => {
+            return Err(Error::new(
+                "match clause requires a pattern".to_string(),
+                clause.get_token().clone(),
+            ));
+        }
+    };
+
+    let (guard, body) = match rest {
+        [keyword, guard, body @ ..] if is_keyword(keyword, "when") => (Some(*guard), body),
+        [keyword] if is_keyword(keyword, "when") => {
+            return Err(Error::new(
+                "\"when\" requires a guard expression".to_string(),
+                keyword.get_token().clone(),
+            ));
+        }
+        _ => (None, rest),
+    };
+
+    let mut bindings = Vec::new();
+    if !match_pattern(run_state, pattern, value, &mut bindings)? {
+        return Ok(None);
+    }
+
+    run_state.push_scope();
+    for (identifier, value) in bindings {
+        run_state
+            .get_local_scope_mut()
+            .define_local(&identifier, value);
+    }
+
+    let res = match guard {
+        Some(guard) => evaluate_condition(run_state, guard),
+        None => Ok(true),
+    }
+    .and_then(|matched| {
+        if !matched {
+            return Ok(None);
+        }
+
+        // the body is always the end of the children
+        let children = clause.get_children();
+        evaluate_block(run_state, &children[children.len() - body.len()..]).map(Some)
+    });
+    run_state.pop_scope();
+
+    res
+}
+
+fn std_pattern_match(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    let args = node.get_children();
+
+    if args.is_empty() {
+        return Err(Error::new(
+            "requires a value to match".to_string(),
+            node.get_token().clone(),
+        ));
+    }
+
+    let value = args[0].evaluate(run_state)?;
+
+    for clause in &args[1..] {
+        if let Some(res) = evaluate_clause(run_state, clause, &value)? {
+            return Ok(res);
+        }
+    }
+
+    Err(Error::new(
+        format!("no pattern matched: {:?}", value),
+        node.get_token().clone(),
+    ))
+}
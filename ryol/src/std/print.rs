@@ -5,12 +5,24 @@ use crate::prelude::*;
 pub fn add_print_lib(run_state: &mut RunState) -> Result<(), Error> {
     run_state.expose_function("print", std_print_print)?;
     run_state.expose_function("println", std_print_println)?;
+    run_state.expose_function("printf", std_print_printf)?;
 
     Ok(())
 }
 
+// a first argument with braces in it is a template like for "fmt", otherwise
+// the arguments are joined together like for "format"
+fn print_string(function_name: &str, args: Vec<Value>) -> Result<String, Error> {
+    match args.first() {
+        Some(Value::String(template)) if template.contains(['{', '}']) => {
+            crate::std::string::fmt_string(function_name, &args)
+        }
+        _ => crate::std::string::format_string(&args),
+    }
+}
+
 pub fn std_print_print(args: Vec<Value>) -> Result<Value, Error> {
-    let output = crate::std::string::format_string(&args)?;
+    let output = print_string("print", args)?;
     print!("{}", output);
 
     // this is probably more efficient but i want to be sure
@@ -23,7 +35,14 @@ pub fn std_print_print(args: Vec<Value>) -> Result<Value, Error> {
 }
 
 pub fn std_print_println(args: Vec<Value>) -> Result<Value, Error> {
-    std_print_print(args)?;
-    println!();
+    let output = print_string("println", args)?;
+    println!("{}", output);
+    Ok(Value::default())
+}
+
+// prints a template like "fmt" would format it, without a new line
+pub fn std_print_printf(args: Vec<Value>) -> Result<Value, Error> {
+    let output = crate::std::string::fmt_string("printf", &args)?;
+    print!("{}", output);
     Ok(Value::default())
 }
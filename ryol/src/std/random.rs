@@ -0,0 +1,175 @@
+use crate::prelude::*;
+use crate::std::maths::{get_argument_count_error, get_non_num_type_error, to_float};
+
+// these need the run state to get to its generator
+pub fn add_random_lib(run_state: &mut RunState) -> Result<(), Error> {
+    run_state.expose_run_state_function("seed-random", std_random_seed)?;
+    run_state.expose_run_state_function("rand-int", std_random_int)?;
+    run_state.expose_run_state_function("rand-float", std_random_float)?;
+    run_state.expose_run_state_function("shuffle", std_random_shuffle)?;
+    run_state.expose_run_state_function("choice", std_random_choice)?;
+    run_state.expose_run_state_function("sample", std_random_sample)?;
+
+    Ok(())
+}
+
+fn get_integer(function_name: &str, arg: &Value) -> Result<i64, Error> {
+    match arg {
+        Value::Integer(integer) => Ok(*integer),
+        _ => Err(Error::new(
+            format!(
+                "non integer type: {:?} in function \"{}\"",
+                arg, function_name
+            ),
+            None,
+        )),
+    }
+}
+
+fn get_float(function_name: &str, arg: &Value) -> Result<f64, Error> {
+    to_float(arg).ok_or_else(|| get_non_num_type_error(function_name, arg))
+}
+
+fn get_elements(function_name: &str, arg: &Value) -> Result<Vec<Value>, Error> {
+    match arg.iter() {
+        Some(iter) => Ok(iter.collect()),
+        None => Err(Error::new(
+            format!(
+                "non iterable type: {:?} in function \"{}\"",
+                arg, function_name
+            ),
+            None,
+        )),
+    }
+}
+
+fn get_empty_range_error(function_name: &str, low: &dyn ToString, high: &dyn ToString) -> Error {
+    Error::new(
+        format!(
+            "range from: {} to: {} is empty in function \"{}\"",
+            low.to_string(),
+            high.to_string(),
+            function_name
+        ),
+        None,
+    )
+}
+
+// moves a random selection of count elements to the front
+fn partial_shuffle(random: &mut Random, elements: &mut [Value], count: usize) {
+    for i in 0..count {
+        let j = i + random.next_below((elements.len() - i) as u64) as usize;
+        elements.swap(i, j);
+    }
+}
+
+fn std_random_seed(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [seed] => {
+            run_state.seed_random(get_integer("seed-random", seed)? as u64);
+            Ok(Value::Null)
+        }
+        _ => Err(get_argument_count_error("seed-random", "one argument")),
+    }
+}
+
+// "(rand-int high)" is the same as "(rand-int 0 high)", high is excluded
+fn std_random_int(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let (low, high) = match args.as_slice() {
+        [high] => (0, get_integer("rand-int", high)?),
+        [low, high] => (
+            get_integer("rand-int", low)?,
+            get_integer("rand-int", high)?,
+        ),
+        _ => return Err(get_argument_count_error("rand-int", "one or two arguments")),
+    };
+
+    if low >= high {
+        return Err(get_empty_range_error("rand-int", &low, &high));
+    }
+
+    // the span can be wider than an i64 but always fits in a u64
+    let span = (high as i128 - low as i128) as u64;
+    Ok(Value::Integer(
+        (low as i128 + run_state.get_random_mut().next_below(span) as i128) as i64,
+    ))
+}
+
+// between 0 and 1 with no arguments, high is excluded
+fn std_random_float(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let (low, high) = match args.as_slice() {
+        [] => (0.0, 1.0),
+        [high] => (0.0, get_float("rand-float", high)?),
+        [low, high] => (
+            get_float("rand-float", low)?,
+            get_float("rand-float", high)?,
+        ),
+        _ => {
+            return Err(get_argument_count_error(
+                "rand-float",
+                "at most two arguments",
+            ));
+        }
+    };
+
+    // nan also gives a difference that is not finite
+    if high <= low || !(high - low).is_finite() {
+        return Err(get_empty_range_error("rand-float", &low, &high));
+    }
+
+    Ok(Value::Float(
+        low + run_state.get_random_mut().next_float() * (high - low),
+    ))
+}
+
+fn std_random_shuffle(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let mut elements = match args.as_slice() {
+        [arg] => get_elements("shuffle", arg)?,
+        _ => return Err(get_argument_count_error("shuffle", "one argument")),
+    };
+
+    let len = elements.len();
+    partial_shuffle(run_state.get_random_mut(), &mut elements, len);
+    Ok(Value::List(elements))
+}
+
+fn std_random_choice(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let mut elements = match args.as_slice() {
+        [arg] => get_elements("choice", arg)?,
+        _ => return Err(get_argument_count_error("choice", "one argument")),
+    };
+
+    if elements.is_empty() {
+        return Err(Error::new(
+            "can not choose from an empty collection in function \"choice\"".to_string(),
+            None,
+        ));
+    }
+
+    let index = run_state.get_random_mut().next_below(elements.len() as u64) as usize;
+    Ok(elements.swap_remove(index))
+}
+
+// picks count different elements, in a random order
+fn std_random_sample(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let (mut elements, count) = match args.as_slice() {
+        [arg, count] => (get_elements("sample", arg)?, get_integer("sample", count)?),
+        _ => return Err(get_argument_count_error("sample", "two arguments")),
+    };
+
+    if count < 0 || count as usize > elements.len() {
+        return Err(Error::new(
+            format!(
+                "can not take a sample of: {} from: {} elements in function \"sample\"",
+                count,
+                elements.len()
+            ),
+            None,
+        ));
+    }
+
+    let count = count as usize;
+    partial_shuffle(run_state.get_random_mut(), &mut elements, count);
+    elements.truncate(count);
+    Ok(Value::List(elements))
+}
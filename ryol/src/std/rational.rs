@@ -0,0 +1,113 @@
+use crate::prelude::*;
+use crate::std::maths::{
+    from_big_integer, from_rational, get_argument_count_error, get_division_by_zero_error,
+    get_non_num_type_error, to_float, to_rational,
+};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, Zero};
+
+pub fn add_rational_lib(run_state: &mut RunState) -> Result<(), Error> {
+    run_state.expose_function("rational", std_rational_rational)?;
+    run_state.expose_function("rational?", std_rational_is_rational)?;
+    run_state.expose_function("numerator", std_rational_numerator)?;
+    run_state.expose_function("denominator", std_rational_denominator)?;
+
+    // conversions between the kinds of number
+    run_state.expose_function("to-float", std_rational_to_float)?;
+    run_state.expose_function("to-integer", std_rational_to_integer)?;
+
+    Ok(())
+}
+
+fn get_single_arg<'a>(function_name: &str, args: &'a [Value]) -> Result<&'a Value, Error> {
+    match args {
+        [arg] => Ok(arg),
+        _ => Err(get_argument_count_error(function_name, "one argument")),
+    }
+}
+
+fn get_non_finite_error(function_name: &str, arg: &Value) -> Error {
+    Error::new(
+        format!(
+            "non finite number: {:?} in function \"{}\"",
+            arg, function_name
+        ),
+        None,
+    )
+}
+
+// floats are converted exactly, so "0.1" becomes the fraction closest to it
+fn to_exact(function_name: &str, arg: &Value) -> Result<BigRational, Error> {
+    match arg {
+        Value::Float(float) => {
+            BigRational::from_float(*float).ok_or_else(|| get_non_finite_error(function_name, arg))
+        }
+        _ => to_rational(arg).ok_or_else(|| get_non_num_type_error(function_name, arg)),
+    }
+}
+
+fn std_rational_rational(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [arg] => Ok(from_rational(to_exact("rational", arg)?)),
+        [numerator, denominator] => {
+            let numerator = to_exact("rational", numerator)?;
+            let denominator = to_exact("rational", denominator)?;
+
+            if denominator.is_zero() {
+                return Err(get_division_by_zero_error("rational"));
+            }
+
+            Ok(from_rational(numerator / denominator))
+        }
+        _ => Err(get_argument_count_error("rational", "one or two arguments")),
+    }
+}
+
+fn std_rational_is_rational(args: Vec<Value>) -> Result<Value, Error> {
+    let arg = get_single_arg("rational?", &args)?;
+    Ok(Value::Boolean(matches!(arg, Value::Rational(_))))
+}
+
+fn std_rational_numerator(args: Vec<Value>) -> Result<Value, Error> {
+    let arg = get_single_arg("numerator", &args)?;
+
+    match to_rational(arg) {
+        Some(rational) => Ok(from_big_integer(rational.numer().clone())),
+        None => Err(get_non_num_type_error("numerator", arg)),
+    }
+}
+
+fn std_rational_denominator(args: Vec<Value>) -> Result<Value, Error> {
+    let arg = get_single_arg("denominator", &args)?;
+
+    match to_rational(arg) {
+        Some(rational) => Ok(from_big_integer(rational.denom().clone())),
+        None => Err(get_non_num_type_error("denominator", arg)),
+    }
+}
+
+fn std_rational_to_float(args: Vec<Value>) -> Result<Value, Error> {
+    let arg = get_single_arg("to-float", &args)?;
+
+    match to_float(arg) {
+        Some(float) => Ok(Value::Float(float)),
+        None => Err(get_non_num_type_error("to-float", arg)),
+    }
+}
+
+// rounds towards zero
+fn std_rational_to_integer(args: Vec<Value>) -> Result<Value, Error> {
+    let arg = get_single_arg("to-integer", &args)?;
+
+    match arg {
+        Value::Float(float) => match BigInt::from_f64(float.trunc()) {
+            Some(big_integer) => Ok(from_big_integer(big_integer)),
+            None => Err(get_non_finite_error("to-integer", arg)),
+        },
+        _ => match to_rational(arg) {
+            Some(rational) => Ok(from_big_integer(rational.to_integer())),
+            None => Err(get_non_num_type_error("to-integer", arg)),
+        },
+    }
+}
@@ -0,0 +1,155 @@
+use crate::prelude::*;
+use crate::std::maths::get_argument_count_error;
+use crate::std::string::get_non_string_type_error;
+use regex::{Captures, Regex};
+
+// patterns always come first, these take the run state so that compiled
+// patterns can be cached in it
+pub fn add_regex_lib(run_state: &mut RunState) -> Result<(), Error> {
+    run_state.expose_run_state_function("re-match?", std_regex_is_match)?;
+    run_state.expose_run_state_function("re-find", std_regex_find)?;
+    run_state.expose_run_state_function("re-find-all", std_regex_find_all)?;
+    run_state.expose_run_state_function("re-replace", std_regex_replace)?;
+    run_state.expose_run_state_function("re-split", std_regex_split)?;
+
+    Ok(())
+}
+
+type RegexFunction = fn(&Regex, &[Value]) -> Result<Value, Error>;
+
+fn get_string<'a>(function_name: &str, arg: &'a Value) -> Result<&'a str, Error> {
+    match arg {
+        Value::String(string) => Ok(string),
+        _ => Err(get_non_string_type_error(function_name, arg)),
+    }
+}
+
+fn compile_and_call(
+    run_state: &mut RunState,
+    function_name: &str,
+    args: Vec<Value>,
+    function: RegexFunction,
+) -> Result<Value, Error> {
+    let (pattern, rest) = match args.split_first() {
+        Some((pattern, rest)) => (get_string(function_name, pattern)?, rest),
+        None => {
+            return Err(get_argument_count_error(
+                function_name,
+                "a pattern and a string",
+            ));
+        }
+    };
+
+    match run_state.get_regex(pattern) {
+        Ok(regex) => function(&regex, rest),
+        Err(error) => Err(Error::new(
+            format!(
+                "invalid pattern: \"{}\" in function \"{}\": {}",
+                pattern, function_name, error
+            ),
+            None,
+        )),
+    }
+}
+
+fn get_single_string<'a>(function_name: &str, args: &'a [Value]) -> Result<&'a str, Error> {
+    match args {
+        [arg] => get_string(function_name, arg),
+        _ => Err(get_argument_count_error(
+            function_name,
+            "a pattern and a string",
+        )),
+    }
+}
+
+// the whole match on its own if there are no groups, otherwise a list of the
+// whole match followed by each group, with null for groups that did not match
+fn captures_to_value(regex: &Regex, captures: &Captures) -> Value {
+    let to_value = |group: Option<regex::Match>| match group {
+        Some(group) => Value::String(group.as_str().to_string()),
+        None => Value::Null,
+    };
+
+    if regex.captures_len() == 1 {
+        return to_value(captures.get(0));
+    }
+
+    Value::List(captures.iter().map(to_value).collect())
+}
+
+// true if the pattern matches anywhere, use "^" and "$" to match everything
+fn regex_is_match(regex: &Regex, args: &[Value]) -> Result<Value, Error> {
+    let string = get_single_string("re-match?", args)?;
+    Ok(Value::Boolean(regex.is_match(string)))
+}
+
+fn regex_find(regex: &Regex, args: &[Value]) -> Result<Value, Error> {
+    let string = get_single_string("re-find", args)?;
+
+    match regex.captures(string) {
+        Some(captures) => Ok(captures_to_value(regex, &captures)),
+        None => Ok(Value::Null),
+    }
+}
+
+fn regex_find_all(regex: &Regex, args: &[Value]) -> Result<Value, Error> {
+    let string = get_single_string("re-find-all", args)?;
+
+    Ok(Value::List(
+        regex
+            .captures_iter(string)
+            .map(|captures| captures_to_value(regex, &captures))
+            .collect(),
+    ))
+}
+
+// replaces every match, "$1" and "${name}" refer to groups
+fn regex_replace(regex: &Regex, args: &[Value]) -> Result<Value, Error> {
+    let (string, replacement) = match args {
+        [string, replacement] => (
+            get_string("re-replace", string)?,
+            get_string("re-replace", replacement)?,
+        ),
+        _ => {
+            return Err(get_argument_count_error(
+                "re-replace",
+                "a pattern, a string and a replacement",
+            ));
+        }
+    };
+
+    Ok(Value::String(
+        regex.replace_all(string, replacement).into_owned(),
+    ))
+}
+
+fn regex_split(regex: &Regex, args: &[Value]) -> Result<Value, Error> {
+    let string = get_single_string("re-split", args)?;
+
+    Ok(Value::List(
+        regex
+            .split(string)
+            .map(|part| Value::String(part.to_string()))
+            .collect(),
+    ))
+}
+
+fn std_regex_is_match(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    compile_and_call(run_state, "re-match?", args, regex_is_match)
+}
+
+fn std_regex_find(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    compile_and_call(run_state, "re-find", args, regex_find)
+}
+
+fn std_regex_find_all(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    compile_and_call(run_state, "re-find-all", args, regex_find_all)
+}
+
+fn std_regex_replace(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    compile_and_call(run_state, "re-replace", args, regex_replace)
+}
+
+fn std_regex_split(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    compile_and_call(run_state, "re-split", args, regex_split)
+}
@@ -0,0 +1,178 @@
+use crate::map::MapKey;
+use crate::prelude::*;
+use crate::std::maths::get_argument_count_error;
+use ::std::hash::{DefaultHasher, Hash, Hasher};
+
+pub fn add_set_lib(run_state: &mut RunState) -> Result<(), Error> {
+    // "set" already sets variables
+    run_state.expose_function("make-set", std_set_make_set)?;
+    run_state.expose_function("to-set", std_set_to_set)?;
+    run_state.expose_function("set?", std_set_is_set)?;
+
+    run_state.expose_function("add", std_set_add)?;
+    run_state.expose_function("remove", std_set_remove)?;
+    run_state.expose_function("member?", std_set_is_member)?;
+    run_state.expose_function("union", std_set_union)?;
+    run_state.expose_function("intersection", std_set_intersection)?;
+    run_state.expose_function("difference", std_set_difference)?;
+    run_state.expose_function("subset?", std_set_is_subset)?;
+
+    run_state.expose_function("hash", std_set_hash)?;
+
+    Ok(())
+}
+
+fn get_non_set_type_error(function_name: &str, arg: &Value) -> Error {
+    Error::new(
+        format!("non set type: {:?} in function \"{}\"", arg, function_name),
+        None,
+    )
+}
+
+fn get_set<'a>(function_name: &str, arg: &'a Value) -> Result<&'a Set, Error> {
+    match arg {
+        Value::Set(set) => Ok(set),
+        _ => Err(get_non_set_type_error(function_name, arg)),
+    }
+}
+
+// splits off the set that every function other than make-set takes first
+fn split_set_arg<'a>(
+    function_name: &str,
+    args: &'a [Value],
+) -> Result<(&'a Set, &'a [Value]), Error> {
+    match args.split_first() {
+        Some((set, rest)) => Ok((get_set(function_name, set)?, rest)),
+        None => Err(get_argument_count_error(
+            function_name,
+            "a set as its first argument",
+        )),
+    }
+}
+
+fn insert_elements(set: &mut Set, elements: impl Iterator<Item = Value>) -> Result<(), Error> {
+    for element in elements {
+        set.insert(element)?;
+    }
+
+    Ok(())
+}
+
+fn std_set_make_set(args: Vec<Value>) -> Result<Value, Error> {
+    let mut set = Set::new();
+    insert_elements(&mut set, args.into_iter())?;
+
+    Ok(Value::Set(set))
+}
+
+// removes duplicates from anything that can be iterated over
+fn std_set_to_set(args: Vec<Value>) -> Result<Value, Error> {
+    let arg = match args.as_slice() {
+        [arg] => arg,
+        _ => return Err(get_argument_count_error("to-set", "one argument")),
+    };
+
+    let mut set = Set::new();
+    match arg.iter() {
+        Some(elements) => insert_elements(&mut set, elements)?,
+        None => {
+            return Err(Error::new(
+                format!("non iterable type: {:?} in function \"to-set\"", arg),
+                None,
+            ));
+        }
+    }
+
+    Ok(Value::Set(set))
+}
+
+fn std_set_is_set(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [arg] => Ok(Value::Boolean(matches!(arg, Value::Set(_)))),
+        _ => Err(get_argument_count_error("set?", "one argument")),
+    }
+}
+
+fn std_set_add(args: Vec<Value>) -> Result<Value, Error> {
+    let (set, elements) = split_set_arg("add", &args)?;
+
+    let mut set = set.clone();
+    insert_elements(&mut set, elements.iter().cloned())?;
+    Ok(Value::Set(set))
+}
+
+fn std_set_remove(args: Vec<Value>) -> Result<Value, Error> {
+    let (set, elements) = split_set_arg("remove", &args)?;
+
+    let mut set = set.clone();
+    for element in elements {
+        set.remove(element);
+    }
+    Ok(Value::Set(set))
+}
+
+fn std_set_is_member(args: Vec<Value>) -> Result<Value, Error> {
+    match split_set_arg("member?", &args)? {
+        (set, [element]) => Ok(Value::Boolean(set.contains(element))),
+        _ => Err(get_argument_count_error("member?", "a set and an element")),
+    }
+}
+
+fn combine_sets(
+    function_name: &str,
+    args: &[Value],
+    combine: fn(&Set, &Set) -> Set,
+) -> Result<Value, Error> {
+    let (first, rest) = split_set_arg(function_name, args)?;
+
+    let mut output = first.clone();
+    for set in rest {
+        output = combine(&output, get_set(function_name, set)?);
+    }
+
+    Ok(Value::Set(output))
+}
+
+fn std_set_union(args: Vec<Value>) -> Result<Value, Error> {
+    combine_sets("union", &args, Set::union)
+}
+
+fn std_set_intersection(args: Vec<Value>) -> Result<Value, Error> {
+    combine_sets("intersection", &args, Set::intersection)
+}
+
+fn std_set_difference(args: Vec<Value>) -> Result<Value, Error> {
+    combine_sets("difference", &args, Set::difference)
+}
+
+fn std_set_is_subset(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [x, y] => Ok(Value::Boolean(
+            get_set("subset?", x)?.is_subset(get_set("subset?", y)?),
+        )),
+        _ => Err(get_argument_count_error("subset?", "two arguments")),
+    }
+}
+
+// the same for equal values in every run, and for values that are the same
+// map key like 1 and 1.0
+fn std_set_hash(args: Vec<Value>) -> Result<Value, Error> {
+    let arg = match args.as_slice() {
+        [arg] => arg,
+        _ => return Err(get_argument_count_error("hash", "one argument")),
+    };
+
+    let key = match MapKey::new(arg.clone()) {
+        Ok(key) => key,
+        Err(_) => {
+            return Err(Error::new(
+                format!("can not hash: {:?} in function \"hash\"", arg),
+                None,
+            ))
+        }
+    };
+
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    Ok(Value::Integer(hasher.finish() as i64))
+}
@@ -0,0 +1,150 @@
+use crate::prelude::*;
+use crate::std::list::{call_value, get_elements, get_function_and_elements};
+use crate::std::logic::order_values;
+use crate::std::maths::get_argument_count_error;
+use ::std::cmp::Ordering;
+
+// every sort is stable, so elements that compare equal keep their order
+pub fn add_sort_lib(run_state: &mut RunState) -> Result<(), Error> {
+    run_state.expose_function("sort", std_sort_sort)?;
+    run_state.expose_run_state_function("sort-by", std_sort_sort_by)?;
+    run_state.expose_run_state_function("sort-with", std_sort_sort_with)?;
+    run_state.expose_run_state_function("min-by", std_sort_min_by)?;
+    run_state.expose_run_state_function("max-by", std_sort_max_by)?;
+
+    Ok(())
+}
+
+// a merge sort rather than the one in std so that comparisons can fail and
+// so that badly behaved comparators can not cause a panic
+fn merge_sort<T>(
+    elements: Vec<T>,
+    compare: &mut impl FnMut(&T, &T) -> Result<Ordering, Error>,
+) -> Result<Vec<T>, Error> {
+    if elements.len() <= 1 {
+        return Ok(elements);
+    }
+
+    let mut left = elements;
+    let right = left.split_off(left.len() / 2);
+    let left = merge_sort(left, compare)?;
+    let right = merge_sort(right, compare)?;
+
+    let mut output = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+
+    while let (Some(x), Some(y)) = (left.peek(), right.peek()) {
+        // taking from the left on ties keeps the sort stable
+        if compare(x, y)? == Ordering::Greater {
+            output.extend(right.next());
+        } else {
+            output.extend(left.next());
+        }
+    }
+
+    output.extend(left);
+    output.extend(right);
+    Ok(output)
+}
+
+// pairs each element with its key so that the key function is called once
+// per element
+fn get_keyed_elements(
+    run_state: &mut RunState,
+    function_name: &str,
+    args: &[Value],
+) -> Result<Vec<(Value, Value)>, Error> {
+    let (function, elements) = get_function_and_elements(function_name, args)?;
+
+    let mut keyed = Vec::with_capacity(elements.len());
+    for element in elements {
+        let key = call_value(run_state, function_name, function, vec![element.clone()])?;
+        keyed.push((key, element));
+    }
+
+    Ok(keyed)
+}
+
+fn std_sort_sort(args: Vec<Value>) -> Result<Value, Error> {
+    let elements = match args.as_slice() {
+        [elements] => get_elements("sort", elements)?,
+        _ => return Err(get_argument_count_error("sort", "one argument")),
+    };
+
+    Ok(Value::List(merge_sort(elements, &mut |x, y| {
+        Ok(order_values(x, y))
+    })?))
+}
+
+fn std_sort_sort_by(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let keyed = get_keyed_elements(run_state, "sort-by", &args)?;
+    let sorted = merge_sort(keyed, &mut |(x, _), (y, _)| Ok(order_values(x, y)))?;
+
+    Ok(Value::List(
+        sorted.into_iter().map(|(_, element)| element).collect(),
+    ))
+}
+
+// the comparator gives back a negative integer, zero or a positive integer
+// for less than, equal and greater than
+fn std_sort_sort_with(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    let (function, elements) = get_function_and_elements("sort-with", &args)?;
+
+    let sorted = merge_sort(elements, &mut |x, y| match call_value(
+        run_state,
+        "sort-with",
+        function,
+        vec![x.clone(), y.clone()],
+    )? {
+        Value::Integer(integer) => Ok(integer.cmp(&0)),
+        value => Err(Error::new(
+            format!(
+                "comparator must return an integer, recieved: {:?} in function \"sort-with\"",
+                value
+            ),
+            None,
+        )),
+    })?;
+
+    Ok(Value::List(sorted))
+}
+
+// gives back the first element with the smallest or largest key
+fn find_extreme_by(
+    run_state: &mut RunState,
+    function_name: &str,
+    args: &[Value],
+    further: Ordering,
+) -> Result<Value, Error> {
+    let mut keyed = get_keyed_elements(run_state, function_name, args)?.into_iter();
+
+    let mut extreme = match keyed.next() {
+        Some(first) => first,
+        None => {
+            return Err(Error::new(
+                format!(
+                    "can not find the extreme of an empty list in function \"{}\"",
+                    function_name
+                ),
+                None,
+            ));
+        }
+    };
+
+    for (key, element) in keyed {
+        if order_values(&key, &extreme.0) == further {
+            extreme = (key, element);
+        }
+    }
+
+    Ok(extreme.1)
+}
+
+fn std_sort_min_by(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    find_extreme_by(run_state, "min-by", &args, Ordering::Less)
+}
+
+fn std_sort_max_by(run_state: &mut RunState, args: Vec<Value>) -> Result<Value, Error> {
+    find_extreme_by(run_state, "max-by", &args, Ordering::Greater)
+}
@@ -1,13 +1,57 @@
 use crate::prelude::*;
+use crate::std::format::format_template;
+use crate::std::maths::get_argument_count_error;
 use ::std::fmt::Write;
 
 pub fn add_string_lib(run_state: &mut RunState) -> Result<(), Error> {
     // could also be called "string" as it is also a conversion function
     run_state.expose_function("format", std_string_format)?;
+    // formats a template, see std::format
+    run_state.expose_function("fmt", std_string_fmt)?;
+
+    // indices and lengths count characters rather than bytes, "length",
+    // "slice", "reverse", "contains?" and "index-of" are in the list library
+    // as they work on both
+    run_state.expose_function("substring", std_string_substring)?;
+    run_state.expose_function("chars", std_string_chars)?;
+
+    run_state.expose_function("split", std_string_split)?;
+    run_state.expose_function("join", std_string_join)?;
+    run_state.expose_function("replace", std_string_replace)?;
+    run_state.expose_function("repeat", std_string_repeat)?;
+
+    run_state.expose_function("starts-with?", std_string_starts_with)?;
+    run_state.expose_function("ends-with?", std_string_ends_with)?;
+
+    run_state.expose_function("upper", std_string_upper)?;
+    run_state.expose_function("lower", std_string_lower)?;
+    run_state.expose_function("trim", std_string_trim)?;
+    run_state.expose_function("trim-left", std_string_trim_left)?;
+    run_state.expose_function("trim-right", std_string_trim_right)?;
+    run_state.expose_function("pad-left", std_string_pad_left)?;
+    run_state.expose_function("pad-right", std_string_pad_right)?;
 
     Ok(())
 }
 
+// the longest string in bytes that "repeat" and the padding functions make,
+// anything longer is almost certainly a mistake that would use up all memory
+const MAX_STRING_LENGTH: usize = 1 << 28;
+
+// length is none when working it out overflowed
+fn check_string_length(function_name: &str, length: Option<usize>) -> Result<(), Error> {
+    match length {
+        Some(length) if length <= MAX_STRING_LENGTH => Ok(()),
+        _ => Err(Error::new(
+            format!(
+                "string would be longer than {} bytes in function \"{}\"",
+                MAX_STRING_LENGTH, function_name
+            ),
+            None,
+        )),
+    }
+}
+
 pub fn format_string(args: &Vec<Value>) -> Result<String, Error> {
     let mut output = String::new();
 
@@ -29,3 +73,379 @@ pub fn format_string(args: &Vec<Value>) -> Result<String, Error> {
 fn std_string_format(args: Vec<Value>) -> Result<Value, Error> {
     Ok(Value::String(format_string(&args)?))
 }
+
+// the first argument is the template and the rest fill its placeholders
+pub fn fmt_string(function_name: &str, args: &[Value]) -> Result<String, Error> {
+    match args {
+        [Value::String(template), rest @ ..] => format_template(template, rest),
+        [arg, ..] => Err(get_non_string_type_error(function_name, arg)),
+        [] => Err(get_argument_count_error(function_name, "a template")),
+    }
+}
+
+fn std_string_fmt(args: Vec<Value>) -> Result<Value, Error> {
+    Ok(Value::String(fmt_string("fmt", &args)?))
+}
+
+pub fn get_non_string_type_error(function_name: &str, arg: &Value) -> Error {
+    Error::new(
+        format!(
+            "non string type: {:?} in function \"{}\"",
+            arg, function_name
+        ),
+        None,
+    )
+}
+
+fn get_string<'a>(function_name: &str, arg: &'a Value) -> Result<&'a str, Error> {
+    match arg {
+        Value::String(string) => Ok(string),
+        _ => Err(get_non_string_type_error(function_name, arg)),
+    }
+}
+
+fn get_single_string<'a>(function_name: &str, args: &'a [Value]) -> Result<&'a str, Error> {
+    match args {
+        [arg] => get_string(function_name, arg),
+        _ => Err(get_argument_count_error(function_name, "one argument")),
+    }
+}
+
+fn get_string_pair<'a>(
+    function_name: &str,
+    args: &'a [Value],
+) -> Result<(&'a str, &'a str), Error> {
+    match args {
+        [x, y] => Ok((get_string(function_name, x)?, get_string(function_name, y)?)),
+        _ => Err(get_argument_count_error(function_name, "two arguments")),
+    }
+}
+
+fn get_integer(function_name: &str, arg: &Value) -> Result<i64, Error> {
+    match arg {
+        Value::Integer(integer) => Ok(*integer),
+        _ => Err(Error::new(
+            format!(
+                "non integer type: {:?} in function \"{}\"",
+                arg, function_name
+            ),
+            None,
+        )),
+    }
+}
+
+pub fn get_index_error(function_name: &str, index: i64, len: usize) -> Error {
+    Error::new(
+        format!(
+            "index: {} is out of range for length: {} in function \"{}\"",
+            index, len, function_name
+        ),
+        None,
+    )
+}
+
+// the position of the n-th character in bytes, n can be one past the end
+fn get_byte_offset(string: &str, n: usize) -> usize {
+    string
+        .char_indices()
+        .nth(n)
+        .map_or(string.len(), |(offset, _)| offset)
+}
+
+// start and end are character indices, with end being excluded. negative
+// indices count back from the end if allowed
+pub fn get_slice_bounds(
+    function_name: &str,
+    args: &[Value],
+    len: usize,
+    allow_negative: bool,
+) -> Result<(usize, usize), Error> {
+    let resolve = |arg: &Value| -> Result<usize, Error> {
+        let index = get_integer(function_name, arg)?;
+        let resolved = if index < 0 && allow_negative {
+            index + len as i64
+        } else {
+            index
+        };
+
+        if resolved < 0 || resolved as usize > len {
+            return Err(get_index_error(function_name, index, len));
+        }
+
+        Ok(resolved as usize)
+    };
+
+    let (start, end) = match args {
+        [start] => (resolve(start)?, len),
+        [start, end] => (resolve(start)?, resolve(end)?),
+        _ => {
+            return Err(get_argument_count_error(
+                function_name,
+                "a start index and an optional end index",
+            ));
+        }
+    };
+
+    if start > end {
+        return Err(Error::new(
+            format!(
+                "start: {} is after end: {} in function \"{}\"",
+                start, end, function_name
+            ),
+            None,
+        ));
+    }
+
+    Ok((start, end))
+}
+
+pub fn string_length(string: &str) -> Value {
+    Value::Integer(string.chars().count() as i64)
+}
+
+pub fn string_slice(
+    function_name: &str,
+    string: &str,
+    bounds: &[Value],
+    allow_negative: bool,
+) -> Result<Value, Error> {
+    let (start, end) = get_slice_bounds(
+        function_name,
+        bounds,
+        string.chars().count(),
+        allow_negative,
+    )?;
+
+    let start_offset = get_byte_offset(string, start);
+    let end_offset = start_offset + get_byte_offset(&string[start_offset..], end - start);
+    Ok(Value::String(string[start_offset..end_offset].to_string()))
+}
+
+// gives back null if the string is not found
+pub fn string_index_of(string: &str, pattern: &str) -> Value {
+    match string.find(pattern) {
+        Some(offset) => Value::Integer(string[..offset].chars().count() as i64),
+        None => Value::Null,
+    }
+}
+
+pub fn string_reverse(string: &str) -> Value {
+    Value::String(string.chars().rev().collect())
+}
+
+fn split_string_arg<'a>(
+    function_name: &str,
+    args: &'a [Value],
+) -> Result<(&'a str, &'a [Value]), Error> {
+    match args.split_first() {
+        Some((string, rest)) => Ok((get_string(function_name, string)?, rest)),
+        None => Err(get_argument_count_error(
+            function_name,
+            "a string and a start index",
+        )),
+    }
+}
+
+fn std_string_substring(args: Vec<Value>) -> Result<Value, Error> {
+    let (string, bounds) = split_string_arg("substring", &args)?;
+    string_slice("substring", string, bounds, false)
+}
+
+fn std_string_chars(args: Vec<Value>) -> Result<Value, Error> {
+    let string = get_single_string("chars", &args)?;
+    Ok(Value::List(
+        string
+            .chars()
+            .map(|c| Value::String(c.to_string()))
+            .collect(),
+    ))
+}
+
+fn get_empty_pattern_error(function_name: &str) -> Error {
+    Error::new(
+        format!("pattern can not be empty in function \"{}\"", function_name),
+        None,
+    )
+}
+
+fn to_string_list<'a>(parts: impl Iterator<Item = &'a str>) -> Value {
+    Value::List(parts.map(|part| Value::String(part.to_string())).collect())
+}
+
+// splits on whitespace if there is no separator
+fn std_string_split(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [string] => Ok(to_string_list(
+            get_string("split", string)?.split_whitespace(),
+        )),
+        [_, _] => {
+            let (string, separator) = get_string_pair("split", &args)?;
+            if separator.is_empty() {
+                return Err(get_empty_pattern_error("split"));
+            }
+
+            Ok(to_string_list(string.split(separator)))
+        }
+        _ => Err(get_argument_count_error(
+            "split",
+            "a string and an optional separator",
+        )),
+    }
+}
+
+fn std_string_join(args: Vec<Value>) -> Result<Value, Error> {
+    let (elements, separator) = match args.as_slice() {
+        [elements] => (elements, ""),
+        [elements, separator] => (elements, get_string("join", separator)?),
+        _ => {
+            return Err(get_argument_count_error(
+                "join",
+                "a list and an optional separator",
+            ));
+        }
+    };
+
+    let elements = match elements {
+        Value::List(list) => list,
+        _ => {
+            return Err(Error::new(
+                format!("non list type: {:?} in function \"join\"", elements),
+                None,
+            ));
+        }
+    };
+
+    let mut strings = Vec::with_capacity(elements.len());
+    for element in elements {
+        strings.push(get_string("join", element)?);
+    }
+
+    Ok(Value::String(strings.join(separator)))
+}
+
+// replaces every occurrence
+fn std_string_replace(args: Vec<Value>) -> Result<Value, Error> {
+    let (string, pattern, replacement) = match args.as_slice() {
+        [string, pattern, replacement] => (
+            get_string("replace", string)?,
+            get_string("replace", pattern)?,
+            get_string("replace", replacement)?,
+        ),
+        _ => return Err(get_argument_count_error("replace", "three arguments")),
+    };
+
+    if pattern.is_empty() {
+        return Err(get_empty_pattern_error("replace"));
+    }
+
+    Ok(Value::String(string.replace(pattern, replacement)))
+}
+
+fn std_string_repeat(args: Vec<Value>) -> Result<Value, Error> {
+    let (string, count) = match args.as_slice() {
+        [string, count] => (get_string("repeat", string)?, get_integer("repeat", count)?),
+        _ => return Err(get_argument_count_error("repeat", "two arguments")),
+    };
+
+    if count < 0 {
+        return Err(Error::new(
+            format!("count: {} is negative in function \"repeat\"", count),
+            None,
+        ));
+    }
+
+    check_string_length("repeat", string.len().checked_mul(count as usize))?;
+    Ok(Value::String(string.repeat(count as usize)))
+}
+
+fn std_string_starts_with(args: Vec<Value>) -> Result<Value, Error> {
+    let (string, prefix) = get_string_pair("starts-with?", &args)?;
+    Ok(Value::Boolean(string.starts_with(prefix)))
+}
+
+fn std_string_ends_with(args: Vec<Value>) -> Result<Value, Error> {
+    let (string, suffix) = get_string_pair("ends-with?", &args)?;
+    Ok(Value::Boolean(string.ends_with(suffix)))
+}
+
+fn std_string_upper(args: Vec<Value>) -> Result<Value, Error> {
+    Ok(Value::String(
+        get_single_string("upper", &args)?.to_uppercase(),
+    ))
+}
+
+fn std_string_lower(args: Vec<Value>) -> Result<Value, Error> {
+    Ok(Value::String(
+        get_single_string("lower", &args)?.to_lowercase(),
+    ))
+}
+
+fn std_string_trim(args: Vec<Value>) -> Result<Value, Error> {
+    Ok(Value::String(
+        get_single_string("trim", &args)?.trim().to_string(),
+    ))
+}
+
+fn std_string_trim_left(args: Vec<Value>) -> Result<Value, Error> {
+    let string = get_single_string("trim-left", &args)?;
+    Ok(Value::String(string.trim_start().to_string()))
+}
+
+fn std_string_trim_right(args: Vec<Value>) -> Result<Value, Error> {
+    let string = get_single_string("trim-right", &args)?;
+    Ok(Value::String(string.trim_end().to_string()))
+}
+
+// pads to a width in characters, with spaces unless another character is given
+fn pad_string(function_name: &str, args: &[Value], pad_left: bool) -> Result<Value, Error> {
+    let (string, width, fill) = match args {
+        [string, width] => (string, width, ' '),
+        [string, width, fill] => {
+            let fill = get_string(function_name, fill)?;
+            let mut chars = fill.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => (string, width, c),
+                _ => {
+                    return Err(Error::new(
+                        format!(
+                            "fill: \"{}\" must be a single character in function \"{}\"",
+                            fill, function_name
+                        ),
+                        None,
+                    ));
+                }
+            }
+        }
+        _ => {
+            return Err(get_argument_count_error(
+                function_name,
+                "a string, a width and an optional fill character",
+            ));
+        }
+    };
+
+    let string = get_string(function_name, string)?;
+    let width = get_integer(function_name, width)?;
+    let count = (width.max(0) as usize).saturating_sub(string.chars().count());
+    check_string_length(
+        function_name,
+        fill.len_utf8()
+            .checked_mul(count)
+            .and_then(|length| length.checked_add(string.len())),
+    )?;
+    let padding = fill.to_string().repeat(count);
+
+    Ok(Value::String(if pad_left {
+        padding + string
+    } else {
+        string.to_string() + &padding
+    }))
+}
+
+fn std_string_pad_left(args: Vec<Value>) -> Result<Value, Error> {
+    pad_string("pad-left", &args, true)
+}
+
+fn std_string_pad_right(args: Vec<Value>) -> Result<Value, Error> {
+    pad_string("pad-right", &args, false)
+}
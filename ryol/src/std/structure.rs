@@ -8,7 +8,7 @@ pub fn add_structure_lib(run_state: &mut RunState) -> Result<(), Error> {
     Ok(())
 }
 
-fn def_struct(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
+fn def_struct(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
     let children = node.get_children();
     if children.is_empty() {
         return Err(Error::new(
@@ -20,20 +20,39 @@ fn def_struct(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
     let structure_identifier = get_identifier(children.first().unwrap())?;
 
     let mut structure_template = StructureTemplate::new();
+    structure_template.set_identifier(structure_identifier);
 
+    // members are either "name" or "(name default)", with the default
+    // evaluated once here
     for child in children.iter().skip(1) {
         let member_identifier = get_identifier(child)?;
-        structure_template.add_member(member_identifier)?;
+
+        match child.get_children().as_slice() {
+            [] => structure_template.add_member(member_identifier)?,
+            [default] => {
+                let default = default.evaluate(run_state)?;
+                structure_template.add_member_with_default(member_identifier, default)?
+            }
+            _ => {
+                return Err(Error::new(
+                    format!(
+                        "member: \"{}\" can only have one default value",
+                        member_identifier
+                    ),
+                    child.get_token().clone(),
+                ));
+            }
+        }
     }
 
     run_state
         .get_local_scope_mut()
         .set_structure_template(structure_identifier, structure_template);
 
-    Ok(Value::default())
+    Ok(Flow::Value(Value::default()))
 }
 
-fn get_member(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
+fn get_member(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
     let children = node.get_children();
     if children.len() != 2 {
         return Err(Error::new(
@@ -48,7 +67,7 @@ fn get_member(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
     match run_state.find_local(structure_identifier) {
         Some(local) => match local {
             Value::Structure(structure) => match structure.get_member(member_identifier) {
-                Some(value) => Ok(value.clone()),
+                Some(value) => Ok(Flow::Value(value.clone())),
                 None => Err(Error::new(
                     format!("structure does not have member: \"{}\"", member_identifier),
                     node.get_token().clone(),
@@ -69,7 +88,7 @@ fn get_member(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
     }
 }
 
-fn set_member(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
+fn set_member(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
     let children = node.get_children();
     if children.len() != 3 {
         return Err(Error::new(
@@ -88,7 +107,7 @@ fn set_member(run_state: &mut RunState, node: &Node) -> Result<Value, Error> {
             Value::Structure(structure) => {
                 structure.set_member(member_identifier, value)?;
 
-                Ok(Value::default())
+                Ok(Flow::Value(Value::default()))
             }
             _ => Err(Error::new(
                 format!(
@@ -0,0 +1,59 @@
+use crate::prelude::*;
+use crate::std::maths::get_argument_count_error;
+
+pub fn add_symbol_lib(run_state: &mut RunState) -> Result<(), Error> {
+    run_state.expose_function("symbol", std_symbol_symbol)?;
+    run_state.expose_function("keyword", std_symbol_keyword)?;
+    run_state.expose_function("symbol?", std_symbol_is_symbol)?;
+    run_state.expose_function("keyword?", std_symbol_is_keyword)?;
+    run_state.expose_function("name", std_symbol_name)?;
+
+    Ok(())
+}
+
+fn get_single_arg<'a>(function_name: &str, args: &'a [Value]) -> Result<&'a Value, Error> {
+    match args {
+        [arg] => Ok(arg),
+        _ => Err(get_argument_count_error(function_name, "one argument")),
+    }
+}
+
+// symbols and keywords can be made from strings or from each other
+fn get_name(function_name: &str, args: &[Value]) -> Result<Symbol, Error> {
+    match get_single_arg(function_name, args)? {
+        Value::String(string) if !string.is_empty() => Ok(Symbol::new(string)),
+        Value::Symbol(symbol) | Value::Keyword(symbol) => Ok(symbol.clone()),
+        arg => Err(Error::new(
+            format!("non name type: {:?} in function \"{}\"", arg, function_name),
+            None,
+        )),
+    }
+}
+
+fn std_symbol_symbol(args: Vec<Value>) -> Result<Value, Error> {
+    Ok(Value::Symbol(get_name("symbol", &args)?))
+}
+
+fn std_symbol_keyword(args: Vec<Value>) -> Result<Value, Error> {
+    Ok(Value::Keyword(get_name("keyword", &args)?))
+}
+
+fn std_symbol_is_symbol(args: Vec<Value>) -> Result<Value, Error> {
+    let arg = get_single_arg("symbol?", &args)?;
+    Ok(Value::Boolean(matches!(arg, Value::Symbol(_))))
+}
+
+fn std_symbol_is_keyword(args: Vec<Value>) -> Result<Value, Error> {
+    let arg = get_single_arg("keyword?", &args)?;
+    Ok(Value::Boolean(matches!(arg, Value::Keyword(_))))
+}
+
+fn std_symbol_name(args: Vec<Value>) -> Result<Value, Error> {
+    match get_single_arg("name", &args)? {
+        Value::Symbol(symbol) | Value::Keyword(symbol) => Ok(Value::String(symbol.to_string())),
+        arg => Err(Error::new(
+            format!("non symbol type: {:?} in function \"name\"", arg),
+            None,
+        )),
+    }
+}
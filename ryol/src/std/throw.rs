@@ -1,12 +1,145 @@
 use crate::prelude::*;
+use crate::std::basic::evaluate_block;
+use crate::std::maths::get_argument_count_error;
+use ::std::rc::Rc;
 
 pub fn add_throw_lib(run_state: &mut RunState) -> Result<(), Error> {
     run_state.expose_function("throw", std_throw)?;
+    run_state.expose_macro("try", std_try)?;
+
+    // for inspecting caught errors
+    run_state.expose_function("error?", std_is_error)?;
+    run_state.expose_function("error-message", std_error_message)?;
+    run_state.expose_function("error-kind", std_error_kind)?;
+    run_state.expose_function("error-line", std_error_line)?;
+    run_state.expose_function("error-col", std_error_col)?;
 
     Ok(())
 }
 
 fn std_throw(args: Vec<Value>) -> Result<Value, Error> {
+    // rethrow caught errors as they were
+    if let [Value::Error(error)] = args.as_slice() {
+        return Err(error.as_ref().clone());
+    }
+
     let output = crate::std::string::format_string(&args)?;
-    Err(Error::new(output, None))
+    Err(Error::new_with_kind(ErrorKind::Thrown, output, None))
+}
+
+fn is_clause(node: &Node, clause_identifier: &str) -> bool {
+    node.is_bracketed()
+        && matches!(get_identifier(node), Ok(identifier) if identifier == clause_identifier)
+}
+
+fn evaluate_catch(run_state: &mut RunState, catch: &Node, error: Error) -> Result<Flow, Error> {
+    let args = catch.get_children();
+
+    if args.is_empty() {
+        return Err(Error::new(
+            "catch requires an identifier for the error".to_string(),
+            catch.get_token().clone(),
+        ));
+    }
+
+    let identifier = get_identifier(&args[0])?;
+
+    run_state.push_scope();
+    run_state
+        .get_local_scope_mut()
+        .define_local(identifier, Value::Error(Rc::new(error)));
+    let res = evaluate_block(run_state, &args[1..]);
+    run_state.pop_scope();
+
+    res
+}
+
+fn std_try(run_state: &mut RunState, node: &Node) -> Result<Flow, Error> {
+    let args = node.get_children();
+
+    let mut body = args.as_slice();
+    let mut catch = None;
+    let mut finally = None;
+
+    // "(try body... (catch e handler...) (finally cleanup...))"
+    if let Some(last) = body.last() {
+        if is_clause(last, "finally") {
+            finally = Some(last);
+            body = &body[..body.len() - 1];
+        }
+    }
+    if let Some(last) = body.last() {
+        if is_clause(last, "catch") {
+            catch = Some(last);
+            body = &body[..body.len() - 1];
+        }
+    }
+
+    // break and continue are not errors so go straight through
+    let res = match (evaluate_block(run_state, body), catch) {
+        (Err(error), Some(catch)) => evaluate_catch(run_state, catch, error),
+        (res, _) => res,
+    };
+
+    if let Some(finally) = finally {
+        // leaving the finally block takes the place of leaving the body
+        let finally_flow = evaluate_block(run_state, finally.get_children())?;
+        if !finally_flow.is_value() {
+            return Ok(finally_flow);
+        }
+    }
+
+    res
+}
+
+fn get_error_arg<'a>(function_name: &str, args: &'a [Value]) -> Result<&'a Error, Error> {
+    match args {
+        [Value::Error(error)] => Ok(error),
+        [arg] => Err(Error::new(
+            format!(
+                "non error type: {:?} in function \"{}\"",
+                arg, function_name
+            ),
+            None,
+        )),
+        _ => Err(get_argument_count_error(function_name, "one argument")),
+    }
+}
+
+fn std_is_error(args: Vec<Value>) -> Result<Value, Error> {
+    match args.as_slice() {
+        [arg] => Ok(Value::Boolean(matches!(arg, Value::Error(_)))),
+        _ => Err(Error::new(
+            "function \"error?\" takes one argument".to_string(),
+            None,
+        )),
+    }
+}
+
+fn std_error_message(args: Vec<Value>) -> Result<Value, Error> {
+    let error = get_error_arg("error-message", &args)?;
+    Ok(Value::String(error.get_message().clone()))
+}
+
+fn std_error_kind(args: Vec<Value>) -> Result<Value, Error> {
+    let error = get_error_arg("error-kind", &args)?;
+    Ok(Value::String(error.get_kind().get_name().to_string()))
+}
+
+fn std_error_line(args: Vec<Value>) -> Result<Value, Error> {
+    let error = get_error_arg("error-line", &args)?;
+
+    match error.get_token() {
+        Some(token) => Ok(Value::Integer(token.get_line_no() as i64)),
+        None => Ok(Value::default()),
+    }
+}
+
+fn std_error_col(args: Vec<Value>) -> Result<Value, Error> {
+    let error = get_error_arg("error-col", &args)?;
+
+    match error.get_token() {
+        Some(token) => Ok(Value::Integer(token.get_col_no() as i64)),
+        None => Ok(Value::default()),
+    }
 }
@@ -1,4 +1,5 @@
 use crate::error::Error;
+use crate::map::detach;
 use crate::value::Value;
 use crate::variable::Variable;
 use ::std::collections::{HashMap, HashSet};
@@ -6,16 +7,31 @@ use ::std::fmt;
 
 #[derive(Debug, Clone)]
 pub struct StructureTemplate {
+    // the name given to "def-struct", empty for templates made by hand
+    identifier: String,
     members: HashSet<String>,
+    // members without a default must be given when constructing with named
+    // arguments
+    defaults: HashMap<String, Value>,
 }
 
 impl StructureTemplate {
     pub fn new() -> Self {
         Self {
+            identifier: String::new(),
             members: HashSet::new(),
+            defaults: HashMap::new(),
         }
     }
 
+    pub fn set_identifier(&mut self, identifier: &str) {
+        self.identifier = identifier.to_string();
+    }
+
+    pub fn get_identifier(&self) -> &String {
+        &self.identifier
+    }
+
     pub fn add_member(&mut self, identifier: &String) -> Result<(), Error> {
         if self.members.contains(identifier) {
             return Err(Error::new(
@@ -32,32 +48,158 @@ impl StructureTemplate {
         Ok(())
     }
 
+    pub fn add_member_with_default(
+        &mut self,
+        identifier: &String,
+        default: Value,
+    ) -> Result<(), Error> {
+        self.add_member(identifier)?;
+        self.defaults.insert(identifier.clone(), default);
+
+        Ok(())
+    }
+
     pub fn get_members(&self) -> &HashSet<String> {
         &self.members
     }
+
+    pub fn get_default(&self, identifier: &String) -> Option<&Value> {
+        self.defaults.get(identifier)
+    }
+}
+
+impl Default for StructureTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone)]
 pub struct StructureInstance {
+    template_identifier: String,
     // todo: eventually use something more quick (perhaps with bytecode and then you
     // can use an offset)
     members: HashMap<String, Variable>,
 }
 
 impl StructureInstance {
+    // members without a default start as null. defaults are detached so that
+    // instances do not share the members of a default structure
     pub fn from_template(structure_template: &StructureTemplate) -> Self {
         let mut members = HashMap::with_capacity(structure_template.get_members().len());
         for member in structure_template.get_members() {
-            members.insert(member.clone(), Variable::new(Value::default()));
+            let value = match structure_template.get_default(member) {
+                Some(default) => detach(default.clone()),
+                None => Value::default(),
+            };
+            members.insert(member.clone(), Variable::new(value));
         }
 
-        Self { members }
+        Self {
+            template_identifier: structure_template.get_identifier().clone(),
+            members,
+        }
+    }
+
+    // every given member must exist, and every member without a default must
+    // be given. only used when members are named, see evaluate_branch_identifier
+    pub fn from_template_with_values(
+        structure_template: &StructureTemplate,
+        values: Vec<(String, Value)>,
+    ) -> Result<Self, Error> {
+        let output = Self::from_template(structure_template);
+
+        let mut given = HashSet::with_capacity(values.len());
+        for (identifier, value) in values {
+            if !output.has_member(&identifier) {
+                return Err(Error::new(
+                    format!(
+                        "structure: \"{}\" does not have member: \"{}\"",
+                        output.template_identifier, identifier
+                    ),
+                    None,
+                ));
+            }
+
+            if !given.insert(identifier.clone()) {
+                return Err(Error::new(
+                    format!("member: \"{}\" is given more than once", identifier),
+                    None,
+                ));
+            }
+
+            output.set_member(&identifier, value)?;
+        }
+
+        let mut missing: Vec<_> = structure_template
+            .get_members()
+            .iter()
+            .filter(|member| {
+                !given.contains(*member) && structure_template.get_default(member).is_none()
+            })
+            .collect();
+        missing.sort();
+
+        if let Some(member) = missing.first() {
+            return Err(Error::new(
+                format!(
+                    "structure: \"{}\" is missing member: \"{}\"",
+                    output.template_identifier, member
+                ),
+                None,
+            ));
+        }
+
+        Ok(output)
+    }
+
+    pub fn get_template_identifier(&self) -> &String {
+        &self.template_identifier
     }
 
     pub fn has_member(&self, identifier: &String) -> bool {
         self.members.contains_key(identifier)
     }
 
+    // sorted by name so that the order does not depend on the hash map
+    pub fn get_sorted_members(&self) -> Vec<(&String, Value)> {
+        let mut members: Vec<_> = self
+            .members
+            .iter()
+            .map(|(identifier, member)| (identifier, member.get()))
+            .collect();
+        members.sort_by_key(|(x, _)| *x);
+        members
+    }
+
+    // a copy that shares no members with self, with each member value passed
+    // through detach_value
+    pub fn detached(&self, detach_value: fn(Value) -> Value) -> Self {
+        Self {
+            template_identifier: self.template_identifier.clone(),
+            members: self
+                .members
+                .iter()
+                .map(|(identifier, member)| {
+                    (
+                        identifier.clone(),
+                        Variable::new(detach_value(member.get())),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    // copies of an instance share their members, so they are identical if
+    // every member is shared
+    pub fn is_identical(&self, other: &Self) -> bool {
+        self.template_identifier == other.template_identifier
+            && self.members.len() == other.members.len()
+            && self.members.iter().all(|(identifier, member)| {
+                matches!(other.members.get(identifier), Some(other) if member.ptr_eq(other))
+            })
+    }
+
     pub fn get_member(&self, identifier: &String) -> Option<Value> {
         Some(self.members.get(identifier)?.get())
     }
@@ -86,6 +228,14 @@ impl StructureInstance {
     }
 }
 
+// only the members are compared so that instances are equal to ones made from
+// a hand built copy of their template
+impl PartialEq for StructureInstance {
+    fn eq(&self, other: &Self) -> bool {
+        self.template_identifier == other.template_identifier && self.members == other.members
+    }
+}
+
 impl fmt::Debug for StructureInstance {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{{")?;
@@ -0,0 +1,88 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::{Rc, Weak};
+
+thread_local! {
+    // every name that is used by a symbol or keyword, so that each name is
+    // only ever stored once. entries are weak and removed when the last
+    // symbol with that name is dropped, so that gensym does not leak
+    static INTERNED_NAMES: RefCell<HashMap<Box<str>, Weak<str>>> = RefCell::new(HashMap::new());
+}
+
+// how many names are interned on this thread, for checking that unused names
+// are freed
+pub fn get_interned_count() -> usize {
+    INTERNED_NAMES.with(|interned_names| interned_names.borrow().len())
+}
+
+// an interned name, which makes comparing two of them a pointer comparison
+#[derive(Clone)]
+pub struct Symbol {
+    name: Rc<str>,
+}
+
+impl Symbol {
+    pub fn new(name: &str) -> Self {
+        let name = INTERNED_NAMES.with(|interned_names| {
+            let mut interned_names = interned_names.borrow_mut();
+
+            match interned_names.get(name).and_then(Weak::upgrade) {
+                Some(interned_name) => interned_name,
+                None => {
+                    let interned_name: Rc<str> = Rc::from(name);
+                    interned_names.insert(Box::from(name), Rc::downgrade(&interned_name));
+                    interned_name
+                }
+            }
+        });
+
+        Self { name }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drop for Symbol {
+    fn drop(&mut self) {
+        if Rc::strong_count(&self.name) != 1 {
+            return;
+        }
+
+        // the table may already be gone when the thread is finishing
+        let _ = INTERNED_NAMES.try_with(|interned_names| {
+            if let Ok(mut interned_names) = interned_names.try_borrow_mut() {
+                interned_names.remove(&*self.name);
+            }
+        });
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.name, &other.name)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
@@ -1,3 +1,5 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
 use std::fmt;
 
 #[derive(Debug)]
@@ -44,9 +46,17 @@ impl fmt::Display for TokeniserError {
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     Identifier(String),
+    // ":name" without the colon
+    Keyword(String),
     LBracket,
     RBracket,
+    LBrace,
+    RBrace,
     Integer(i64),
+    // integers that are too big for an i64
+    BigInteger(BigInt),
+    // "1/3", which is never a whole number
+    Rational(BigRational),
     Float(f64),
     String(String),
 }
@@ -70,6 +80,14 @@ impl Token {
     pub fn get_token_type(&self) -> &TokenType {
         &self.token_type
     }
+
+    pub fn get_line_no(&self) -> usize {
+        self.line_no
+    }
+
+    pub fn get_col_no(&self) -> usize {
+        self.col_no
+    }
 }
 
 impl fmt::Display for Token {
@@ -111,13 +129,24 @@ impl TokeniserState {
         self.current_token.clear();
     }
 
-    pub fn try_push_token(&mut self) {
+    pub fn try_push_token(&mut self) -> Result<(), TokeniserError> {
         if self.current_token.is_empty() {
-            return;
+            return Ok(());
         }
 
-        if let Ok(integer) = self.current_token.parse::<i64>() {
+        if self.current_token.len() > 1 && self.current_token.starts_with(':') {
+            self.push_token(
+                TokenType::Keyword(self.current_token[1..].to_string()),
+                self.current_token.len(),
+            );
+        } else if let Ok(integer) = self.current_token.parse::<i64>() {
             self.push_token(TokenType::Integer(integer), self.current_token.len());
+        } else if let Some(big_integer) = parse_big_integer(&self.current_token) {
+            self.push_token(TokenType::BigInteger(big_integer), self.current_token.len());
+        } else if let Some(token_type) = parse_rational(&self.current_token)
+            .map_err(|message| TokeniserError::new(self, message))?
+        {
+            self.push_token(token_type, self.current_token.len());
         } else if let Ok(float) = self.current_token.parse::<f64>() {
             self.push_token(TokenType::Float(float), self.current_token.len());
         } else {
@@ -126,6 +155,8 @@ impl TokeniserState {
                 self.current_token.len(),
             );
         }
+
+        Ok(())
     }
 
     pub fn get_tokens(self) -> Vec<Token> {
@@ -133,6 +164,52 @@ impl TokeniserState {
     }
 }
 
+// only plain digits (with an optional sign) so that things like "1e400" are
+// still read as floats
+fn parse_big_integer(token: &str) -> Option<BigInt> {
+    let digits = token.strip_prefix(['-', '+']).unwrap_or(token);
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    token.parse().ok()
+}
+
+// "1/3" and the like, whole numbers such as "4/2" are turned into integers
+fn parse_rational(token: &str) -> Result<Option<TokenType>, String> {
+    let (numerator, denominator) = match token.split_once('/') {
+        Some(parts) => parts,
+        None => return Ok(None),
+    };
+
+    // only the numerator can have a sign
+    if denominator.starts_with(['-', '+']) {
+        return Ok(None);
+    }
+
+    let (numerator, denominator) =
+        match (parse_big_integer(numerator), parse_big_integer(denominator)) {
+            (Some(numerator), Some(denominator)) => (numerator, denominator),
+            _ => return Ok(None),
+        };
+
+    if denominator == BigInt::from(0) {
+        return Err(format!("rational: \"{}\" has a zero denominator", token));
+    }
+
+    let rational = BigRational::new(numerator, denominator);
+    if !rational.is_integer() {
+        return Ok(Some(TokenType::Rational(rational)));
+    }
+
+    let integer = rational.to_integer();
+    Ok(Some(match i64::try_from(&integer) {
+        Ok(integer) => TokenType::Integer(integer),
+        Err(_) => TokenType::BigInteger(integer),
+    }))
+}
+
 pub fn tokenise(source: &str) -> Result<Vec<Token>, TokeniserError> {
     let mut tokeniser_state = TokeniserState::new(source);
 
@@ -149,22 +226,30 @@ pub fn tokenise(source: &str) -> Result<Vec<Token>, TokeniserError> {
         match tokeniser_mode {
             TokeniserMode::Normal => match c {
                 '(' => {
-                    tokeniser_state.try_push_token();
+                    tokeniser_state.try_push_token()?;
                     tokeniser_state.push_token(TokenType::LBracket, 1);
                 }
                 ')' => {
-                    tokeniser_state.try_push_token();
+                    tokeniser_state.try_push_token()?;
                     tokeniser_state.push_token(TokenType::RBracket, 1);
                 }
+                '{' => {
+                    tokeniser_state.try_push_token()?;
+                    tokeniser_state.push_token(TokenType::LBrace, 1);
+                }
+                '}' => {
+                    tokeniser_state.try_push_token()?;
+                    tokeniser_state.push_token(TokenType::RBrace, 1);
+                }
                 ' ' | '\t' | '\r' | '\n' => {
-                    tokeniser_state.try_push_token();
+                    tokeniser_state.try_push_token()?;
                 }
                 '"' => {
-                    tokeniser_state.try_push_token();
+                    tokeniser_state.try_push_token()?;
                     tokeniser_mode = TokeniserMode::String;
                 }
                 ';' => {
-                    tokeniser_state.try_push_token();
+                    tokeniser_state.try_push_token()?;
                     tokeniser_mode = TokeniserMode::SingleLineComment;
                 }
                 _ => {
@@ -233,7 +318,7 @@ pub fn tokenise(source: &str) -> Result<Vec<Token>, TokeniserError> {
         }
     }
 
-    tokeniser_state.try_push_token();
+    tokeniser_state.try_push_token()?;
 
     Ok(tokeniser_state.get_tokens())
 }
@@ -287,9 +372,40 @@ mod tests {
         match_tokens("1", vec![TokenType::Integer(1)]);
         match_tokens("-1", vec![TokenType::Integer(-1)]);
         match_tokens("1.04", vec![TokenType::Float(1.04)]);
+        match_tokens(
+            "9223372036854775808",
+            vec![TokenType::BigInteger(
+                "9223372036854775808".parse().unwrap(),
+            )],
+        );
+        match_tokens(
+            "-99999999999999999999",
+            vec![TokenType::BigInteger(
+                "-99999999999999999999".parse().unwrap(),
+            )],
+        );
+        match_tokens("1e400", vec![TokenType::Float(f64::INFINITY)]);
         match_tokens("\"Hello\"", vec![TokenType::String("Hello".to_string())]);
     }
 
+    #[test]
+    fn rational_tests() {
+        match_tokens(
+            "1/3",
+            vec![TokenType::Rational(BigRational::new(1.into(), 3.into()))],
+        );
+        match_tokens(
+            "-2/6",
+            vec![TokenType::Rational(BigRational::new((-1).into(), 3.into()))],
+        );
+        match_tokens("4/2", vec![TokenType::Integer(2)]);
+        match_tokens("1/-3", vec![TokenType::Identifier("1/-3".to_string())]);
+        match_tokens("1/x", vec![TokenType::Identifier("1/x".to_string())]);
+        match_tokens("/", vec![TokenType::Identifier("/".to_string())]);
+
+        assert!(tokenise("1/0").is_err());
+    }
+
     #[test]
     fn string_escape_tests() {
         // this was fun to do
@@ -338,4 +454,43 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn keyword_tests() {
+        match_tokens(":a", vec![TokenType::Keyword("a".to_string())]);
+        match_tokens(":", vec![TokenType::Identifier(":".to_string())]);
+        match_tokens(
+            "(f :key-name)",
+            vec![
+                TokenType::LBracket,
+                TokenType::Identifier("f".to_string()),
+                TokenType::Keyword("key-name".to_string()),
+                TokenType::RBracket,
+            ],
+        );
+    }
+
+    #[test]
+    fn map_literal_tests() {
+        match_tokens(
+            "{\"a\" 1}",
+            vec![
+                TokenType::LBrace,
+                TokenType::String("a".to_string()),
+                TokenType::Integer(1),
+                TokenType::RBrace,
+            ],
+        );
+
+        match_tokens(
+            "{a{}}",
+            vec![
+                TokenType::LBrace,
+                TokenType::Identifier("a".to_string()),
+                TokenType::LBrace,
+                TokenType::RBrace,
+                TokenType::RBrace,
+            ],
+        );
+    }
 }
@@ -1,7 +1,20 @@
-use crate::function::{NativeFunction, NativeMacro};
+use crate::collections::{Deque, PriorityQueue, SortedMap};
+use crate::error::Error;
+use crate::function::{
+    format_parameters, Function, NativeFunction, NativeMacro, NativeRunStateFunction,
+};
+use crate::map::Map;
+use crate::range::Range;
+use crate::set::Set;
 use crate::structure::StructureInstance;
+use crate::symbol::Symbol;
+use num_bigint::BigInt;
+use num_rational::BigRational;
 use std::cmp;
 use std::fmt::{self, Write};
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::rc::Rc;
 
 #[derive(Clone, Default)]
 pub enum Value {
@@ -10,18 +23,89 @@ pub enum Value {
 
     Boolean(bool),
     Integer(i64),
+    // only for integers that do not fit into an i64
+    BigInteger(BigInt),
+    // only for numbers that are not whole, in their lowest terms
+    Rational(BigRational),
     Float(f64),
     String(String),
+    Symbol(Symbol),
+    // ":name", which evaluates to itself
+    Keyword(Symbol),
 
     List(Vec<Value>),
+    Range(Range),
+    Map(Map),
+    Set(Set),
+    // shared between copies rather than copied
+    Deque(Deque),
+    PriorityQueue(PriorityQueue),
+    SortedMap(SortedMap),
 
     Structure(StructureInstance),
 
+    Error(Rc<Error>),
+
+    Function(Function),
+    Macro(Function),
     NativeFunction(NativeFunction),
+    NativeRunStateFunction(NativeRunStateFunction),
     NativeMacro(NativeMacro),
 }
 
-fn compare_list(x_list: &Vec<Value>, y_list: &Vec<Value>) -> bool {
+impl Value {
+    // values that can be map keys or set elements. nan is not equal to itself
+    // so could never be found again, the shared collections can change, and
+    // errors and callables have no useful idea of equality
+    pub fn is_hashable(&self) -> bool {
+        match self {
+            Value::Float(float) => !float.is_nan(),
+            Value::List(list) => list.iter().all(Value::is_hashable),
+            Value::Structure(structure) => structure
+                .get_sorted_members()
+                .iter()
+                .all(|(_, value)| value.is_hashable()),
+            Value::Deque(_)
+            | Value::PriorityQueue(_)
+            | Value::SortedMap(_)
+            | Value::Error(_)
+            | Value::Function(_)
+            | Value::Macro(_)
+            | Value::NativeFunction(_)
+            | Value::NativeRunStateFunction(_)
+            | Value::NativeMacro(_) => false,
+            _ => true,
+        }
+    }
+
+    // gives back none if the value can not be iterated over
+    pub fn iter(&self) -> Option<Box<dyn Iterator<Item = Value> + '_>> {
+        match self {
+            Value::List(list) => Some(Box::new(list.iter().cloned())),
+            Value::String(string) => Some(Box::new(
+                string.chars().map(|c| Value::String(c.to_string())),
+            )),
+            Value::Range(range) => Some(Box::new(range.iter().map(Value::Integer))),
+            Value::Map(map) => {
+                Some(Box::new(map.iter().map(|(key, value)| {
+                    Value::List(vec![key.clone(), value.clone()])
+                })))
+            }
+            Value::Set(set) => Some(Box::new(set.iter().cloned())),
+            // copied as they can be changed while being iterated over
+            Value::Deque(deque) => Some(Box::new(deque.to_vec().into_iter())),
+            Value::PriorityQueue(queue) => Some(Box::new(queue.to_vec().into_iter())),
+            Value::SortedMap(map) => Some(Box::new(
+                map.to_vec()
+                    .into_iter()
+                    .map(|(key, value)| Value::List(vec![key, value])),
+            )),
+            _ => None,
+        }
+    }
+}
+
+fn compare_list(x_list: &[Value], y_list: &[Value]) -> bool {
     if x_list.len() != y_list.len() {
         return false;
     }
@@ -41,11 +125,27 @@ impl cmp::PartialEq for Value {
             (Value::Null, Value::Null) => true,
             (Value::Boolean(x), Value::Boolean(y)) => x == y,
             (Value::Integer(x), Value::Integer(y)) => x == y,
+            (Value::BigInteger(x), Value::BigInteger(y)) => x == y,
+            (Value::Rational(x), Value::Rational(y)) => x == y,
             (Value::Float(x), Value::Float(y)) => x == y,
             (Value::String(x), Value::String(y)) => x == y,
+            (Value::Symbol(x), Value::Symbol(y)) => x == y,
+            (Value::Keyword(x), Value::Keyword(y)) => x == y,
             (Value::List(x_list), Value::List(y_list)) => compare_list(x_list, y_list),
+            (Value::Range(x), Value::Range(y)) => x == y,
+            (Value::Map(x), Value::Map(y)) => x == y,
+            (Value::Set(x), Value::Set(y)) => x == y,
+            (Value::Deque(x), Value::Deque(y)) => x == y,
+            (Value::PriorityQueue(x), Value::PriorityQueue(y)) => x == y,
+            (Value::SortedMap(x), Value::SortedMap(y)) => x == y,
             (Value::Structure(x_struct), Value::Structure(y_struct)) => x_struct == y_struct,
-            (Value::NativeFunction(x), Value::NativeFunction(y)) => x == y,
+            (Value::Error(x), Value::Error(y)) => Rc::ptr_eq(x, y),
+            (Value::Function(x), Value::Function(y)) => x == y,
+            (Value::Macro(x), Value::Macro(y)) => x == y,
+            (Value::NativeFunction(x), Value::NativeFunction(y)) => std::ptr::fn_addr_eq(*x, *y),
+            (Value::NativeRunStateFunction(x), Value::NativeRunStateFunction(y)) => {
+                std::ptr::fn_addr_eq(*x, *y)
+            }
             (Value::NativeMacro(x), Value::NativeMacro(y)) => {
                 std::ptr::eq(x as *const NativeMacro, y as *const NativeMacro)
             }
@@ -54,6 +154,54 @@ impl cmp::PartialEq for Value {
     }
 }
 
+// agrees with ==, so values of different types never hash the same and 0.0
+// hashes the same as -0.0. every nan hashes the same but as nan is never
+// equal to anything it can not be used as a key. structures hash by their
+// template and the contents of their members, so should not be changed while
+// used as a key
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+
+        match self {
+            Value::Null => {}
+            Value::Boolean(boolean) => boolean.hash(state),
+            Value::Integer(integer) => integer.hash(state),
+            Value::BigInteger(big_integer) => big_integer.hash(state),
+            Value::Rational(rational) => rational.hash(state),
+            Value::Float(float) => {
+                let float = if *float == 0.0 {
+                    0.0
+                } else if float.is_nan() {
+                    f64::NAN
+                } else {
+                    *float
+                };
+                float.to_bits().hash(state)
+            }
+            Value::String(string) => string.hash(state),
+            Value::Symbol(symbol) | Value::Keyword(symbol) => symbol.hash(state),
+            Value::List(list) => list.hash(state),
+            Value::Range(range) => range.hash(state),
+            Value::Map(map) => map.hash(state),
+            Value::Set(set) => set.hash(state),
+            Value::Structure(structure) => {
+                structure.get_template_identifier().hash(state);
+                structure.get_sorted_members().hash(state)
+            }
+            Value::Error(error) => Rc::as_ptr(error).hash(state),
+            // these can not be keys so only need to agree with ==
+            Value::Deque(_) | Value::PriorityQueue(_) | Value::SortedMap(_) => {}
+            // these are only ever equal to themselves
+            Value::Function(_)
+            | Value::Macro(_)
+            | Value::NativeFunction(_)
+            | Value::NativeRunStateFunction(_)
+            | Value::NativeMacro(_) => {}
+        }
+    }
+}
+
 impl fmt::Debug for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -63,14 +211,33 @@ impl fmt::Debug for Value {
                 Value::Null => "Value::Null".to_string(),
                 Value::Boolean(boolean) => format!("Value::Boolean({})", boolean),
                 Value::Integer(integer) => format!("Value::Integer({})", integer),
+                Value::BigInteger(big_integer) => format!("Value::BigInteger({})", big_integer),
+                Value::Rational(rational) => format!("Value::Rational({})", rational),
                 Value::Float(float) => format!("Value::Float({})", float),
                 Value::String(string) => format!("Value::String(\"{}\")", string),
+                Value::Symbol(symbol) => format!("Value::Symbol({})", symbol),
+                Value::Keyword(keyword) => format!("Value::Keyword(:{})", keyword),
                 Value::List(list) => format!("{:?}", list),
+                Value::Range(range) => format!("Value::{:?}", range),
+                Value::Map(map) => format!("Value::Map({:?})", map),
+                Value::Set(set) => format!("Value::Set({:?})", set),
+                Value::Deque(deque) => format!("Value::Deque({:?})", deque),
+                Value::PriorityQueue(queue) => format!("Value::PriorityQueue({:?})", queue),
+                Value::SortedMap(map) => format!("Value::SortedMap({:?})", map),
                 Value::Structure(structure) => format!("Value::Structure({:?})", structure),
+                Value::Error(error) => format!("Value::Error({:?})", error),
+                Value::Function(function) => format!("Value::{:?}", function),
+                Value::Macro(function) => {
+                    format!("Value::Macro({})", format_parameters(function))
+                }
                 Value::NativeFunction(native_function) => format!(
                     "Value::NativeFunction({:#x})",
                     native_function as *const NativeFunction as u64
                 ),
+                Value::NativeRunStateFunction(native_function) => format!(
+                    "Value::NativeRunStateFunction({:#x})",
+                    native_function as *const NativeRunStateFunction as u64
+                ),
                 Value::NativeMacro(native_macro) => format!(
                     "Value::NativeMacro({:#x})",
                     native_macro as *const NativeMacro as u64
@@ -80,7 +247,7 @@ impl fmt::Debug for Value {
     }
 }
 
-pub fn list_to_string(list: &Vec<Value>) -> Result<String, fmt::Error> {
+pub fn list_to_string(list: &[Value]) -> Result<String, fmt::Error> {
     let mut output = String::new();
 
     write!(&mut output, "(list ")?;
@@ -102,6 +269,8 @@ impl fmt::Display for Value {
             Value::Null => "()".to_string(),
             Value::Boolean(boolean) => format!("{}", boolean),
             Value::Integer(integer) => format!("{}", integer),
+            Value::BigInteger(big_integer) => format!("{}", big_integer),
+            Value::Rational(rational) => format!("{}", rational),
             Value::Float(float) => {
                 // ensure always has 1 dp of precision
                 if *float % 1.0 == 0.0 {
@@ -111,13 +280,29 @@ impl fmt::Display for Value {
                 }
             }
             Value::String(string) => string.clone(),
+            Value::Symbol(symbol) => symbol.to_string(),
+            Value::Keyword(keyword) => format!(":{}", keyword),
             Value::List(list) => list_to_string(list)?,
+            Value::Range(range) => format!("{}", range),
+            Value::Map(map) => format!("{}", map),
+            Value::Set(set) => format!("{}", set),
+            Value::Deque(deque) => format!("{}", deque),
+            Value::PriorityQueue(queue) => format!("{}", queue),
+            Value::SortedMap(map) => format!("{}", map),
             Value::Structure(structure) => format!("{}", structure),
+            Value::Error(error) => format!("{}", error),
+            Value::Function(function) => format!("{:?}", function),
+            Value::Macro(function) => format!("Macro({})", format_parameters(function)),
             Value::NativeFunction(native_function) => {
                 let func_ptr = native_function as *const NativeFunction;
 
                 format!("NativeFunction at {:#x}", func_ptr as u64)
             }
+            Value::NativeRunStateFunction(native_function) => {
+                let func_ptr = native_function as *const NativeRunStateFunction;
+
+                format!("NativeRunStateFunction at {:#x}", func_ptr as u64)
+            }
             Value::NativeMacro(native_macro) => {
                 let func_ptr = native_macro as *const NativeMacro;
 
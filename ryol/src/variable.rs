@@ -16,7 +16,7 @@ impl VariableInstance {
         }
     }
 
-    pub fn _new_const(value: Value) -> Self {
+    pub fn new_const(value: Value) -> Self {
         Self {
             value,
             is_const: true,
@@ -50,10 +50,9 @@ impl Variable {
         }
     }
 
-    pub fn _new_const(value: Value) -> Self {
-        // todo: make const
+    pub fn new_const(value: Value) -> Self {
         Self {
-            inner: Rc::new(RefCell::new(VariableInstance::new(value)))
+            inner: Rc::new(RefCell::new(VariableInstance::new_const(value)))
         }
     }
 
@@ -68,4 +67,9 @@ impl Variable {
     pub fn is_const(&self) -> bool {
         self.inner.borrow().is_const()
     }
+
+    // true if both refer to the same storage rather than just equal values
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
 }
@@ -165,3 +165,232 @@ fn times_tests() {
 
     assert_eq!(eval(source).unwrap(), Value::Integer(5));
 }
+
+#[test]
+fn let_tests() {
+    assert!(eval("(let)").is_err());
+    assert!(eval("(let (x))").is_err());
+    assert!(eval("(let (x 1 y))").is_err());
+    assert_eq!(eval("(let () 5)").unwrap(), Value::Integer(5));
+    assert_eq!(eval("(let (x 1))").unwrap(), Value::Null);
+    assert_eq!(eval("(let (x 1) x)").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(let (x 1 y 2) (+ x y))").unwrap(), Value::Integer(3));
+
+    // later bindings can use earlier ones
+    assert_eq!(
+        eval("(let (x 1 y (+ x 1)) (* x y))").unwrap(),
+        Value::Integer(2)
+    );
+
+    // bindings are dropped at the end of the block
+    assert!(eval("(let (x 1) x) x").is_err());
+
+    // let shadows rather than overwrites
+    assert_eq!(eval("(set x 1) (let (x 2) x)").unwrap(), Value::Integer(2));
+    assert_eq!(
+        eval("(set x 1) (let (x 2) x) x").unwrap(),
+        Value::Integer(1)
+    );
+    assert_eq!(
+        eval("(const x 1) (let (x 2) (set x 3) x)").unwrap(),
+        Value::Integer(3)
+    );
+}
+
+#[test]
+fn do_tests() {
+    assert_eq!(eval("(do)").unwrap(), Value::Null);
+    assert_eq!(eval("(do 1 2 3)").unwrap(), Value::Integer(3));
+
+    // new variables are local to the block
+    assert!(eval("(do (set x 1)) x").is_err());
+    assert_eq!(eval("(do (set x 1) x)").unwrap(), Value::Integer(1));
+}
+
+#[test]
+fn nested_scope_set_tests() {
+    // set updates the nearest existing binding
+    assert_eq!(
+        eval("(set x 1) (do (set x 2)) x").unwrap(),
+        Value::Integer(2)
+    );
+    assert_eq!(
+        eval("(set x 1) (let (x 2) (do (set x 3))) x").unwrap(),
+        Value::Integer(1)
+    );
+    assert_eq!(
+        eval("(let (x 1) (do (set x 2)) x)").unwrap(),
+        Value::Integer(2)
+    );
+
+    // const is only defined in the innermost scope
+    assert_eq!(
+        eval("(const x 1) (do (const x 2) x)").unwrap(),
+        Value::Integer(2)
+    );
+    assert_eq!(
+        eval("(const x 1) (do (const x 2)) x").unwrap(),
+        Value::Integer(1)
+    );
+
+    // the scope is dropped even if the block fails
+    let mut run_state = RunState::new();
+    assert!(run_state.eval("(let (x 1) (throw \"error\"))").is_err());
+    assert!(run_state.eval("x").is_err());
+}
+
+#[test]
+fn times_index_tests() {
+    assert!(eval("(times)").is_err());
+    assert!(eval("(times 5)").is_err());
+    assert!(eval("(times 5 1 2 3)").is_err());
+    assert!(eval("(times 5 1 2)").is_err());
+
+    let source = r#"
+        (set total 0)
+        (times 5 i (set total (+ total i)))
+        total
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(1 + 2 + 3 + 4));
+
+    // the index only exists inside of the loop
+    assert!(eval("(times 5 i ()) i").is_err());
+}
+
+#[test]
+fn while_tests() {
+    assert!(eval("(while)").is_err());
+    assert!(eval("(while 1)").is_err());
+    assert_eq!(eval("(while false)").unwrap(), Value::Null);
+
+    let source = r#"
+        (set x 0)
+        (while (< x 10) (set x (+ x 1)))
+        x
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(10));
+}
+
+#[test]
+fn loop_break_continue_tests() {
+    let source = r#"
+        (set x 0)
+        (loop
+            (set x (+ x 1))
+            (if (= x 5) (break)))
+        x
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(5));
+
+    // break can carry a value out of the loop
+    let source = r#"
+        (set x 0)
+        (loop
+            (set x (+ x 1))
+            (if (> (* x x) 50) (break x)))
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(8));
+    assert_eq!(eval("(while true (break 1))").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(times 5 (break 2))").unwrap(), Value::Integer(2));
+    assert_eq!(eval("(times 5 break)").unwrap(), Value::Null);
+
+    let source = r#"
+        (set total 0)
+        (times 10 i
+            (do
+                (if (= i 5) (continue))
+                (set total (+ total i))))
+        total
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(45 - 5));
+
+    // break only leaves the innermost loop
+    let source = r#"
+        (set count 0)
+        (times 3 (loop (set count (+ count 1)) (break)))
+        count
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(3));
+
+    // break and continue outside of a loop are errors
+    assert!(eval("(break)").is_err());
+    assert!(eval("(continue)").is_err());
+    assert!(eval("(break 1 2)").is_err());
+    assert!(eval("(continue 1)").is_err());
+    assert!(eval("(defn f () (break)) (loop (f))").is_err());
+
+    // they leave blocks rather than giving back a value, so can not be used
+    // where a value is needed
+    assert_eq!(
+        eval("(loop (let (x 1) (match x (1 (break :one)))))").unwrap(),
+        eval(":one").unwrap()
+    );
+    assert!(eval("(loop (set x (break 1)))").is_err());
+    assert!(eval("(loop (+ 1 (continue)))").is_err());
+}
+
+#[test]
+fn for_tests() {
+    assert!(eval("(for)").is_err());
+    assert!(eval("(for x)").is_err());
+    assert!(eval("(for x 5 x)").is_err());
+    assert_eq!(eval("(for x (list))").unwrap(), Value::Null);
+
+    let source = r#"
+        (set total 0)
+        (for x (list 1 2 3) (set total (+ total x)))
+        total
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(6));
+
+    let source = r#"
+        (set total 0)
+        (for-each x (range 0 10 2) (set total (+ total x)))
+        total
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(2 + 4 + 6 + 8));
+
+    let source = r#"
+        (set output "")
+        (for c "abc" (set output (format c output)))
+        output
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::String("cba".to_string()));
+
+    // the identifier only exists inside of the loop
+    assert!(eval("(for x (list 1) ()) x").is_err());
+
+    // break and continue work the same as other loops
+    assert_eq!(
+        eval("(for x (range 10) (if (= x 3) (break x)))").unwrap(),
+        Value::Integer(3)
+    );
+    let source = r#"
+        (set total 0)
+        (for x (range 5) (if (= x 2) (continue)) (set total (+ total x)))
+        total
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(8));
+}
+
+#[test]
+fn for_collect_tests() {
+    assert_eq!(
+        eval("(for x (list 1 2 3) collect (* x x))").unwrap(),
+        eval("(list 1 4 9)").unwrap()
+    );
+    assert_eq!(
+        eval("(for c \"ab\" collect (format c c))").unwrap(),
+        eval("(list \"aa\" \"bb\")").unwrap()
+    );
+    assert_eq!(
+        eval("(for x (range 3) collect)").unwrap(),
+        eval("(list () () ())").unwrap()
+    );
+
+    // continue skips the element and break stops collecting
+    assert_eq!(
+        eval("(for x (range 6) collect (if (= x 1) (continue)) (if (= x 4) (break)) x)").unwrap(),
+        eval("(list 0 2 3)").unwrap()
+    );
+}
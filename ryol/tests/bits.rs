@@ -0,0 +1,70 @@
+use ryol::prelude::*;
+
+#[test]
+fn bitwise_tests() {
+    assert_eq!(eval("(bit-and 12 10)").unwrap(), Value::Integer(12 & 10));
+    assert_eq!(
+        eval("(bit-or 12 10 1)").unwrap(),
+        Value::Integer(12 | 10 | 1)
+    );
+    assert_eq!(eval("(bit-xor 12 10)").unwrap(), Value::Integer(12 ^ 10));
+    assert_eq!(eval("(bit-not 0)").unwrap(), Value::Integer(-1));
+    assert_eq!(eval("(bit-and 7)").unwrap(), Value::Integer(7));
+
+    assert!(eval("(bit-and)").is_err());
+    assert!(eval("(bit-or 1 2.0)").is_err());
+    assert!(eval("(bit-not 100000000000000000000)").is_err());
+}
+
+#[test]
+fn shift_tests() {
+    assert_eq!(eval("(shl 1 4)").unwrap(), Value::Integer(16));
+    assert_eq!(eval("(shr -16 2)").unwrap(), Value::Integer(-4));
+
+    // shl grows into a big integer instead of dropping bits, and shr works on
+    // big integers too
+    assert_eq!(
+        eval("(shl 1 63)").unwrap(),
+        Value::BigInteger("9223372036854775808".parse().unwrap())
+    );
+    assert_eq!(eval("(shl -1 63)").unwrap(), Value::Integer(i64::MIN));
+    assert_eq!(
+        eval("(= (shl 3 100) (* 3 (pow 2 100)))").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(eval("(shr (shl 5 100) 100)").unwrap(), Value::Integer(5));
+    assert_eq!(eval("(shr -1 100)").unwrap(), Value::Integer(-1));
+    assert_eq!(eval("(shr 1 100)").unwrap(), Value::Integer(0));
+    assert_eq!(
+        eval("(shr-logical -16 2)").unwrap(),
+        Value::Integer(((-16i64 as u64) >> 2) as i64)
+    );
+
+    assert!(eval("(shl 1 -1)").is_err());
+    assert!(eval("(shl 1 100000000000)").is_err());
+    assert!(eval("(shl 1.5 1)").is_err());
+    assert!(eval("(shr-logical 1 64)").is_err());
+    assert!(eval("(shr 1 -1)").is_err());
+    assert!(eval("(shr-logical 1 \"a\")").is_err());
+}
+
+#[test]
+fn bit_counting_tests() {
+    assert_eq!(eval("(popcount 255)").unwrap(), Value::Integer(8));
+    assert_eq!(eval("(popcount -1)").unwrap(), Value::Integer(64));
+    assert_eq!(eval("(leading-zeros 1)").unwrap(), Value::Integer(63));
+    assert_eq!(eval("(trailing-zeros 8)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(trailing-zeros 0)").unwrap(), Value::Integer(64));
+}
+
+#[test]
+fn single_bit_tests() {
+    assert_eq!(eval("(bit-test 5 2)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(bit-test 5 1)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(bit-set 5 1)").unwrap(), Value::Integer(7));
+    assert_eq!(eval("(bit-clear 5 0)").unwrap(), Value::Integer(4));
+    assert_eq!(eval("(bit-test -1 63)").unwrap(), Value::Boolean(true));
+
+    assert!(eval("(bit-set 5 64)").is_err());
+    assert!(eval("(bit-test 5)").is_err());
+}
@@ -0,0 +1,208 @@
+mod common;
+
+use common::{boolean, display};
+use ryol::prelude::*;
+
+#[test]
+fn deque_tests() {
+    assert_eq!(display("(make-deque 1 2 3)"), "(make-deque 1 2 3)");
+    assert_eq!(
+        display("(set d (make-deque 2)) (push-front d 1 0) (push-back d 3) d"),
+        "(make-deque 0 1 2 3)"
+    );
+    assert_eq!(
+        eval("(set d (make-deque 1 2 3)) (list (pop-front d) (pop-back d) (length d))").unwrap(),
+        Value::List(vec![
+            Value::Integer(1),
+            Value::Integer(3),
+            Value::Integer(1)
+        ])
+    );
+    assert_eq!(
+        eval("(set d (make-deque 1 2)) (list (peek-front d) (peek-back d) (length d))").unwrap(),
+        Value::List(vec![
+            Value::Integer(1),
+            Value::Integer(2),
+            Value::Integer(2)
+        ])
+    );
+
+    // copies share the same deque
+    assert_eq!(
+        eval("(set d (make-deque)) (set alias d) (push-back alias 1) (length d)").unwrap(),
+        Value::Integer(1)
+    );
+    assert!(boolean("(set d (make-deque)) (identical? d d)"));
+    assert!(!boolean("(identical? (make-deque 1) (make-deque 1))"));
+    assert!(boolean("(= (make-deque 1) (make-deque 1))"));
+
+    assert!(boolean("(deque? (make-deque))"));
+    assert!(!boolean("(deque? (list))"));
+
+    assert!(eval("(pop-front (make-deque))").is_err());
+    assert!(eval("(peek-back (make-deque))").is_err());
+    assert!(eval("(push-back (list) 1)").is_err());
+    assert!(eval("(pop-back (make-deque 1) 2)").is_err());
+
+    // a deque can not hold itself, even through another collection
+    assert!(eval("(set d (make-deque 1)) (push-back d d)").is_err());
+    assert!(eval("(set d (make-deque)) (push-front d (list 1 {:a d}))").is_err());
+    assert!(eval("(set d (make-deque)) (push-back d (make-deque d))").is_err());
+    assert!(eval(
+        "(def-struct s x) (set d (make-deque)) (set v (s)) (set-member v x d) (push-back d v)"
+    )
+    .is_err());
+    assert_eq!(
+        display("(set d (make-deque)) (push-back d (make-deque 1)) (push-back d (make-deque 1)) d"),
+        "(make-deque (make-deque 1) (make-deque 1))"
+    );
+}
+
+#[test]
+fn heap_tests() {
+    assert_eq!(
+        eval("(set h (make-heap)) (heap-push h 3 1 2) (list (heap-pop h) (heap-pop h) (heap-peek h))")
+            .unwrap(),
+        Value::List(vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)])
+    );
+    assert_eq!(
+        display("(set h (make-heap)) (heap-push h 3 1 2 1)"),
+        "(make-heap 1 1 2 3)"
+    );
+    assert_eq!(
+        eval("(length (heap-push (make-heap) 1 2))").unwrap(),
+        Value::Integer(2)
+    );
+
+    // the key function gives the priority, ties come out in the order they
+    // went in
+    assert_eq!(
+        display("(heap-push (make-heap (fn (x) (first x))) (list 2 :a) (list 1 :b) (list 2 :c) (list 1 :d))"),
+        "(make-heap (list 1 :b) (list 1 :d) (list 2 :a) (list 2 :c))"
+    );
+    assert_eq!(
+        display("(heap-push (make-heap (fn (x) (- x))) 1 3 2)"),
+        "(make-heap 3 2 1)"
+    );
+
+    // heap-push is a value like any other function
+    assert_eq!(
+        display("(set h (make-heap)) (map heap-push (list h h) (list 2 1)) h"),
+        "(make-heap 1 2)"
+    );
+
+    assert!(boolean("(heap? (make-heap))"));
+    assert!(!boolean("(heap? (make-deque))"));
+
+    assert!(eval("(set h (make-heap (fn (x) 0))) (heap-push h (list h))").is_err());
+    assert!(eval("(heap-pop (make-heap))").is_err());
+    assert!(eval("(heap-peek (make-heap))").is_err());
+    assert!(eval("(make-heap 1)").is_err());
+    assert!(eval("(heap-push (make-heap) (fn (x) x))").is_err());
+    assert!(eval("(heap-push (make-heap (fn (x) (throw \"bad\"))) 1)").is_err());
+}
+
+#[test]
+fn sorted_map_tests() {
+    assert_eq!(
+        display("(make-sorted-map 3 :c 1 :a 2 :b)"),
+        "(make-sorted-map 1 :a 2 :b 3 :c)"
+    );
+    assert_eq!(
+        display("(set m (make-sorted-map)) (sorted-map-set m :b 2) (sorted-map-set m :a 1) m"),
+        "(make-sorted-map :a 1 :b 2)"
+    );
+    assert_eq!(
+        eval("(sorted-map-get (make-sorted-map 1 :a) 1)").unwrap(),
+        Value::Keyword(Symbol::new("a"))
+    );
+    assert_eq!(
+        eval("(sorted-map-get (make-sorted-map 1 :a) 2 :none)").unwrap(),
+        Value::Keyword(Symbol::new("none"))
+    );
+    assert!(boolean("(sorted-map-has? (make-sorted-map 1 :a) 1)"));
+    assert!(!boolean("(sorted-map-has? (make-sorted-map 1 :a) 2)"));
+    assert_eq!(
+        display("(set m (make-sorted-map 1 :a 2 :b)) (sorted-map-remove m 1) m"),
+        "(make-sorted-map 2 :b)"
+    );
+
+    assert_eq!(
+        display("(sorted-map-range (make-sorted-map 1 :a 2 :b 3 :c 4 :d) 2 4)"),
+        "(list (list 2 :b) (list 3 :c))"
+    );
+    assert_eq!(
+        display("(sorted-map-range (make-sorted-map 1 :a 2 :b) 1.5 10)"),
+        "(list (list 2 :b))"
+    );
+    assert_eq!(
+        display("(sorted-map-range (make-sorted-map 1 :a 2 :b) 2 1)"),
+        "(list )"
+    );
+
+    assert_eq!(
+        display("(set m (make-sorted-map 1 :a 2 :b 3 :c)) (list (sorted-map-first m) (sorted-map-last m))"),
+        "(list (list 1 :a) (list 3 :c))"
+    );
+    assert_eq!(
+        display("(set m (make-sorted-map 1 :a 2 :b 3 :c)) (sorted-map-pop-first m) (sorted-map-pop-last m) m"),
+        "(make-sorted-map 2 :b)"
+    );
+
+    // keys are the same when they are for maps and sets
+    assert_eq!(
+        eval("(sorted-map-get (make-sorted-map 1 :a) 1.0)").unwrap(),
+        eval("(map-get {1 :a} 1.0)").unwrap()
+    );
+    assert_eq!(
+        display("(make-sorted-map 1 :a 1.0 :b 1/2 :c 0.5 :d)"),
+        "(make-sorted-map 1/2 :d 1 :b)"
+    );
+    assert_eq!(
+        eval("(sorted-map-has? (make-sorted-map 0.1 :a) 1/10)").unwrap(),
+        eval("(member? (make-set 0.1) 1/10)").unwrap()
+    );
+    assert_eq!(
+        eval("(def-struct a x) (def-struct b x) (length (make-sorted-map (a x 1) 1 (b x 1) 2))")
+            .unwrap(),
+        Value::Integer(2)
+    );
+    assert_eq!(
+        eval("(def-struct a x) (def-struct b x) (length (make-map (a x 1) 1 (b x 1) 2))").unwrap(),
+        Value::Integer(2)
+    );
+    assert_eq!(
+        eval("(def-struct a x) (def-struct b x) (length (make-set (a x 1) (b x 1)))").unwrap(),
+        Value::Integer(2)
+    );
+
+    assert!(boolean("(sorted-map? (make-sorted-map))"));
+    assert!(!boolean("(sorted-map? {})"));
+
+    assert!(eval("(set m (make-sorted-map)) (sorted-map-set m 1 m)").is_err());
+    assert!(eval("(set m (make-sorted-map)) (sorted-map-set m 1 (make-deque m))").is_err());
+    assert!(eval("(make-sorted-map 1)").is_err());
+    assert!(eval("(make-sorted-map (fn (x) x) 1)").is_err());
+    assert!(eval("(sorted-map-first (make-sorted-map))").is_err());
+    assert!(eval("(sorted-map-pop-last (make-sorted-map))").is_err());
+}
+
+#[test]
+fn collection_iteration_tests() {
+    assert_eq!(
+        display("(for x (make-deque 1 2 3) collect (* x 2))"),
+        "(list 2 4 6)"
+    );
+    assert_eq!(
+        display("(map (fn (x) x) (heap-push (make-heap) 3 1 2))"),
+        "(list 1 2 3)"
+    );
+    assert_eq!(
+        display("(for entry (make-sorted-map 2 :b 1 :a) collect (first entry))"),
+        "(list 1 2)"
+    );
+    assert_eq!(
+        display("(sort (list (make-deque 2) (make-deque 1 2) (make-deque 1)))"),
+        "(list (make-deque 1) (make-deque 1 2) (make-deque 2))"
+    );
+}
@@ -0,0 +1,27 @@
+// helpers shared between the test files, each file only uses some of them
+#![allow(dead_code)]
+
+use ryol::prelude::*;
+
+pub fn boolean(source: &str) -> bool {
+    match eval(source).unwrap() {
+        Value::Boolean(boolean) => boolean,
+        value => panic!("expected a boolean, recieved: {:?}", value),
+    }
+}
+
+pub fn display(source: &str) -> String {
+    format!("{}", eval(source).unwrap())
+}
+
+pub fn string(string: &str) -> Value {
+    Value::String(string.to_string())
+}
+
+pub fn strings(strings: &[&str]) -> Value {
+    Value::List(strings.iter().map(|s| string(s)).collect())
+}
+
+pub fn integers(integers: &[i64]) -> Value {
+    Value::List(integers.iter().map(|i| Value::Integer(*i)).collect())
+}
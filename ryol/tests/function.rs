@@ -0,0 +1,194 @@
+use ryol::prelude::*;
+use ryol::EvalError;
+
+#[test]
+fn function_definition_syntax() {
+    assert!(eval("(fn)").is_err());
+    assert!(eval("(fn ())").is_ok());
+    assert!(eval("(fn (x) x)").is_ok());
+    assert!(eval("(fn (x x) x)").is_err());
+    assert!(eval("(fn (x (y)) x)").is_err());
+    assert!(eval("(defn)").is_err());
+    assert!(eval("(defn f)").is_err());
+    assert!(eval("(defn f ())").is_ok());
+    assert!(eval("(defn f (x y) (+ x y))").is_ok());
+}
+
+#[test]
+fn function_call_tests() {
+    assert_eq!(eval("(defn f () 5) (f)").unwrap(), Value::Integer(5));
+    assert_eq!(
+        eval("(defn f (x) (* x 2)) (f 4)").unwrap(),
+        Value::Integer(8)
+    );
+    assert_eq!(
+        eval("(defn f (x y) (+ x y)) (f 1 (f 2 3))").unwrap(),
+        Value::Integer(6)
+    );
+    assert_eq!(
+        eval("(set f (fn (x) (- x 1))) (f 3)").unwrap(),
+        Value::Integer(2)
+    );
+
+    // the body can have multiple expressions
+    assert_eq!(
+        eval("(defn f (x) (set y x) (+ y 1)) (f 1)").unwrap(),
+        Value::Integer(2)
+    );
+
+    // wrong number of arguments
+    assert!(eval("(defn f (x) x) (f)").is_err());
+    assert!(eval("(defn f (x) x) (f 1 2)").is_err());
+}
+
+#[test]
+fn function_value_tests() {
+    // functions are values that can be passed around without being called
+    assert_eq!(
+        eval("(defn f (x) (+ x 1)) (set g f) (g 1)").unwrap(),
+        Value::Integer(2)
+    );
+    assert_eq!(
+        eval("(defn apply (func x) (func x)) (defn inc (x) (+ x 1)) (apply inc 1)").unwrap(),
+        Value::Integer(2)
+    );
+    assert!(matches!(eval("(fn (x) x)").unwrap(), Value::Function(_)));
+}
+
+#[test]
+fn function_scope_tests() {
+    // parameters do not leak out of the function
+    assert!(eval("(defn f (x) x) (f 1) x").is_err());
+
+    // parameters shadow globals
+    assert_eq!(
+        eval("(set x 1) (defn f (x) x) (f 2)").unwrap(),
+        Value::Integer(2)
+    );
+    assert_eq!(
+        eval("(set x 1) (defn f (x) x) (f 2) x").unwrap(),
+        Value::Integer(1)
+    );
+}
+
+#[test]
+fn recursion_tests() {
+    let source = r#"
+        (defn fact (n)
+            (if (same n 0) 1 else (* n (fact (- n 1)))))
+        (fact 10)
+    "#;
+
+    let mut run_state = RunState::new();
+    run_state.expose_function("same", same).unwrap();
+    assert_eq!(run_state.eval(source).unwrap(), Value::Integer(3628800));
+
+    // calls not in tail position can still go thousands deep
+    let source = r#"
+        (defn fact (n) (if (= n 0) 1 else (* n (fact (- n 1)))))
+        (= (fact 100) (* 100 (fact 99)))
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Boolean(true));
+
+    let source = r#"
+        (defn sum (n) (if (= n 0) 0 else (+ n (sum (- n 1)))))
+        (sum 3000)
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(3000 * 3001 / 2));
+}
+
+#[test]
+fn closure_tests() {
+    let source = r#"
+        (defn make-adder (n) (fn (x) (+ x n)))
+        (set add-two (make-adder 2))
+        (add-two 5)
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(7));
+
+    // captured variables are shared with the closure
+    let source = r#"
+        (defn make-counter ()
+            (set count 0)
+            (fn () (set count (+ count 1)) count))
+        (set counter (make-counter))
+        (counter)
+        (counter)
+        (counter)
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(3));
+
+    // closures can call themselves
+    let source = r#"
+        (defn outer ()
+            (defn countdown (n) (if (same n 0) 0 else (countdown (- n 1))))
+            countdown)
+        (set f (outer))
+        (f 5)
+    "#;
+    let mut run_state = RunState::new();
+    run_state.expose_function("same", same).unwrap();
+    assert_eq!(run_state.eval(source).unwrap(), Value::Integer(0));
+}
+
+fn same(args: Vec<Value>) -> Result<Value, Error> {
+    Ok(Value::Boolean(args[0] == args[1]))
+}
+
+#[test]
+fn tail_call_tests() {
+    // calls in tail position do not use up the recursion limit
+    let source = r#"
+        (defn count-down (n acc) (if (= n 0) acc else (count-down (- n 1) (+ acc 1))))
+        (count-down 100000 0)
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(100000));
+
+    let source = r#"
+        (defn even? (n) (if (= n 0) true else (odd? (- n 1))))
+        (defn odd? (n) (if (= n 0) false else (even? (- n 1))))
+        (odd? 100001)
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Boolean(true));
+
+    let source = r#"
+        (defn f (n) (let (m (- n 1)) (do (if (= m 0) "done" else (f m)))))
+        (f 100000)
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::String("done".to_string()));
+}
+
+#[test]
+fn recursion_limit_tests() {
+    let source = "(defn f (n) (if (= n 0) 0 else (+ 1 (f (- n 1))))) (f 100000)";
+    match eval(source) {
+        Err(EvalError::RuntimeError(error)) => {
+            assert_eq!(error.get_kind(), ErrorKind::Recursion);
+            assert!(error.get_token().is_some());
+        }
+        res => panic!("expected a recursion error, recieved: {:?}", res),
+    }
+
+    // the error can be caught
+    let source = r#"
+        (defn f (n) (+ 1 (f n)))
+        (try (f 0) (catch e (error-kind e)))
+    "#;
+    assert_eq!(
+        eval(source).unwrap(),
+        Value::String("recursion".to_string())
+    );
+
+    let mut run_state = RunState::new();
+    run_state.set_max_depth(64);
+    assert!(run_state
+        .eval("(defn f (n) (if (= n 0) 0 else (+ 1 (f (- n 1))))) (f 4)")
+        .is_ok());
+    assert!(run_state.eval("(f 100)").is_err());
+}
+
+#[test]
+fn nesting_limit_tests() {
+    let source = format!("{}1{}", "(+ 1 ".repeat(5000), ")".repeat(5000));
+    assert!(matches!(eval(&source), Err(EvalError::ParserError(_))));
+}
@@ -1,3 +1,6 @@
+mod common;
+
+use common::integers;
 use ryol::prelude::*;
 
 #[test]
@@ -19,3 +22,195 @@ fn variable_list_tests() {
         Value::List(vec![Value::Integer(5), Value::String("asdf".to_string())])
     );
 }
+
+#[test]
+fn range_tests() {
+    assert!(eval("(range)").is_err());
+    assert!(eval("(range 1.5)").is_err());
+    assert!(eval("(range 0 10 0)").is_err());
+    assert!(eval("(range 1 2 3 4)").is_err());
+
+    assert_eq!(
+        eval("(range 5)").unwrap(),
+        Value::Range(Range::new(0, 5, 1).unwrap())
+    );
+    assert_eq!(
+        eval("(format (range 0 10 2))").unwrap(),
+        Value::String("(range 0 10 2)".to_string())
+    );
+
+    assert_eq!(
+        eval("(for x (range 3) collect x)").unwrap(),
+        eval("(list 0 1 2)").unwrap()
+    );
+    assert_eq!(
+        eval("(for x (range 1 4) collect x)").unwrap(),
+        eval("(list 1 2 3)").unwrap()
+    );
+    assert_eq!(
+        eval("(for x (range 0 10 3) collect x)").unwrap(),
+        eval("(list 0 3 6 9)").unwrap()
+    );
+    assert_eq!(
+        eval("(for x (range 3 0 -1) collect x)").unwrap(),
+        eval("(list 3 2 1)").unwrap()
+    );
+    assert_eq!(
+        eval("(for x (range 3 0) collect x)").unwrap(),
+        eval("(list)").unwrap()
+    );
+}
+
+#[test]
+fn length_tests() {
+    assert_eq!(eval("(length (list 1 2 3))").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(length (list))").unwrap(), Value::Integer(0));
+    assert_eq!(eval("(length (range 0 10 3))").unwrap(), Value::Integer(4));
+    assert_eq!(eval("(length (range 10 0 -4))").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(length (range 5 0))").unwrap(), Value::Integer(0));
+    assert_eq!(eval("(length {1 2})").unwrap(), Value::Integer(1));
+    assert!(eval("(length 5)").is_err());
+}
+
+#[test]
+fn indexing_tests() {
+    assert_eq!(eval("(nth (list 1 2 3) 1)").unwrap(), Value::Integer(2));
+    assert_eq!(
+        eval("(set-nth (list 1 2 3) 0 5)").unwrap(),
+        integers(&[5, 2, 3])
+    );
+    assert_eq!(eval("(first (list 1 2 3))").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(rest (list 1 2 3))").unwrap(), integers(&[2, 3]));
+    assert_eq!(eval("(last (list 1 2 3))").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(first (list))").unwrap(), Value::Null);
+    assert_eq!(eval("(rest (list))").unwrap(), integers(&[]));
+
+    assert!(eval("(nth (list 1 2 3) -1)").is_err());
+    assert!(eval("(set-nth (list) 0 1)").is_err());
+    assert!(eval("(nth 5 0)").is_err());
+
+    // out of range indexing is an error at the call
+    match eval("(do\n  (nth (list 1 2 3) 3))") {
+        Err(ryol::EvalError::RuntimeError(error)) => {
+            assert!(error.get_message().contains("out of range"));
+            assert_eq!(error.get_token().clone().unwrap().get_line_no(), 2);
+        }
+        res => panic!("expected a runtime error, recieved: {:?}", res),
+    }
+}
+
+#[test]
+fn slicing_tests() {
+    assert_eq!(
+        eval("(slice (list 1 2 3 4) 1 3)").unwrap(),
+        integers(&[2, 3])
+    );
+    assert_eq!(
+        eval("(slice (list 1 2 3 4) -2)").unwrap(),
+        integers(&[3, 4])
+    );
+    assert_eq!(
+        eval("(reverse (list 1 2 3))").unwrap(),
+        integers(&[3, 2, 1])
+    );
+    assert!(eval("(slice (list 1 2) 3)").is_err());
+
+    assert_eq!(eval("(append (list 1) 2 3)").unwrap(), integers(&[1, 2, 3]));
+    assert_eq!(
+        eval("(concat (list 1) (list) (list 2 3))").unwrap(),
+        integers(&[1, 2, 3])
+    );
+    assert_eq!(
+        eval("(flatten (list 1 (list 2 (list 3)) (list)))").unwrap(),
+        integers(&[1, 2, 3])
+    );
+    assert_eq!(
+        eval("(zip (list 1 2 3) (range 4 6))").unwrap(),
+        Value::List(vec![integers(&[1, 4]), integers(&[2, 5])])
+    );
+    assert!(eval("(concat (list 1) 2)").is_err());
+}
+
+#[test]
+fn searching_tests() {
+    assert_eq!(
+        eval("(contains? (list 1 2 3) 2.0)").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(contains? (list 1 2 3) 4)").unwrap(),
+        Value::Boolean(false)
+    );
+    assert_eq!(
+        eval("(index-of (list \"a\" \"b\") \"b\")").unwrap(),
+        Value::Integer(1)
+    );
+    assert_eq!(eval("(index-of (list 1) 2)").unwrap(), Value::Null);
+}
+
+#[test]
+fn higher_order_tests() {
+    assert_eq!(
+        eval("(map (fn (x) (* x x)) (list 1 2 3))").unwrap(),
+        integers(&[1, 4, 9])
+    );
+    assert_eq!(
+        eval("(map + (list 1 2 3) (range 10 12))").unwrap(),
+        integers(&[11, 13])
+    );
+    assert_eq!(
+        eval("(defn even? (x) (= (mod x 2) 0)) (filter even? (range 7))").unwrap(),
+        integers(&[0, 2, 4, 6])
+    );
+
+    assert_eq!(eval("(reduce + (list 1 2 3))").unwrap(), Value::Integer(6));
+    assert_eq!(
+        eval("(reduce + 10 (list 1 2 3))").unwrap(),
+        Value::Integer(16)
+    );
+    assert_eq!(
+        eval("(fold (fn (acc x) (append acc x)) (list) \"ab\")").unwrap(),
+        Value::List(vec![
+            Value::String("a".to_string()),
+            Value::String("b".to_string())
+        ])
+    );
+    assert!(eval("(reduce + (list))").is_err());
+
+    assert_eq!(
+        eval("(any? (fn (x) (> x 2)) (list 1 2 3))").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(all? (fn (x) (> x 2)) (list 1 2 3))").unwrap(),
+        Value::Boolean(false)
+    );
+    assert_eq!(
+        eval("(find (fn (x) (> x 1)) (list 1 2 3))").unwrap(),
+        Value::Integer(2)
+    );
+    assert_eq!(
+        eval("(find (fn (x) (> x 5)) (list 1 2 3))").unwrap(),
+        Value::Null
+    );
+
+    // closures keep their scope
+    assert_eq!(
+        eval("(let (n 10) (map (fn (x) (+ x n)) (list 1 2)))").unwrap(),
+        integers(&[11, 12])
+    );
+
+    assert!(eval("(map 5 (list 1))").is_err());
+    assert!(eval("(filter (fn (x) x) (list 1))").is_err());
+    assert!(eval("(map (fn (x y) x) (list 1))").is_err());
+
+    // the higher order functions are values themselves
+    assert_eq!(
+        eval("(set m map) (m (fn (x) (* x 2)) (list 1 2))").unwrap(),
+        integers(&[2, 4])
+    );
+    assert_eq!(
+        eval("(map map (list (fn (x) (+ x 1))) (list (list 1 2)))").unwrap(),
+        Value::List(vec![integers(&[2, 3])])
+    );
+}
@@ -0,0 +1,178 @@
+use ryol::prelude::*;
+
+#[test]
+fn equality_tests() {
+    assert!(eval("(=)").is_err());
+    assert!(eval("(= 1)").is_err());
+
+    assert_eq!(eval("(= 1 1)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(= 1 2)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(= 1 1 1)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(= 1 1 2)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(= 1 1.0)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(= 1.5 1)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(= 0.5 1/2)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(= 0.0 -0.0)").unwrap(), Value::Boolean(true));
+    assert_eq!(
+        eval("(= 9007199254740993 9007199254740992.0)").unwrap(),
+        Value::Boolean(false)
+    );
+    assert_eq!(eval("(= nan nan)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(= \"a\" \"a\")").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(= \"a\" 1)").unwrap(), Value::Boolean(false));
+    assert_eq!(
+        eval("(= (list 1 2) (list 1 2))").unwrap(),
+        Value::Boolean(true)
+    );
+
+    assert_eq!(eval("(!= 1 2)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(!= 1 1.0)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(≠ 1 2)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(!= 1 2 3)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(!= 1 1 2)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(!= 1 2 1)").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn ordering_tests() {
+    assert!(eval("(< 1)").is_err());
+    assert!(eval("(< 1 \"a\")").is_err());
+    assert!(eval("(< true false)").is_err());
+
+    assert_eq!(eval("(< 1 2)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(< 2 1)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(< 1 1)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(< 1 2 3)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(< 1 3 2)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(< 1 1.5)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(< 1.5 1)").unwrap(), Value::Boolean(false));
+
+    assert_eq!(eval("(<= 1 1)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(<= 1 1 2)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(≤ 2 1)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(> 2 1)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(> 1 1)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(>= 1 1.0)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(≥ 3 2 1)").unwrap(), Value::Boolean(true));
+
+    // floats are compared by their exact value rather than rounding the other
+    // number to a float
+    assert_eq!(
+        eval("(< 9007199254740992.0 9007199254740993)").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(eval("(< 0.1 1/10)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(> 0.1 1/10)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(< 1/3 0.5)").unwrap(), Value::Boolean(true));
+    assert_eq!(
+        eval("(< (- 0.0 1e400) 1 1e400)").unwrap(),
+        Value::Boolean(true)
+    );
+
+    // strings are compared lexicographically
+    assert_eq!(eval("(< \"a\" \"b\")").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(< \"ab\" \"b\")").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(> \"b\" \"abc\")").unwrap(), Value::Boolean(true));
+}
+
+#[test]
+fn logical_operator_tests() {
+    assert_eq!(eval("(and)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(and true true)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(and true false)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(or)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(or false true)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(or false false)").unwrap(), Value::Boolean(false));
+    assert!(eval("(and 1)").is_err());
+    assert!(eval("(or 1)").is_err());
+
+    assert_eq!(eval("(not true)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(not false)").unwrap(), Value::Boolean(true));
+    assert!(eval("(not)").is_err());
+    assert!(eval("(not 1)").is_err());
+    assert!(eval("(not true true)").is_err());
+
+    // the right hand side is not evaluated when it is not needed
+    assert_eq!(
+        eval("(and false (throw \"error\"))").unwrap(),
+        Value::Boolean(false)
+    );
+    assert_eq!(
+        eval("(or true (throw \"error\"))").unwrap(),
+        Value::Boolean(true)
+    );
+    assert!(eval("(and true (throw \"error\"))").is_err());
+}
+
+#[test]
+fn comparison_in_if_tests() {
+    assert_eq!(
+        eval("(set x 5) (if (< x 3) \"small\" else \"big\")").unwrap(),
+        Value::String("big".to_string())
+    );
+    assert_eq!(
+        eval("(set x 5) (if (and (> x 3) (not (= x 4))) 1 else 2)").unwrap(),
+        Value::Integer(1)
+    );
+}
+
+#[test]
+fn deep_equality_tests() {
+    assert_eq!(
+        eval("(= (list 1 (list 2)) (list 1.0 (list 2)))").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(= (list 1 2) (list 1))").unwrap(),
+        Value::Boolean(false)
+    );
+    assert_eq!(eval("(= {:a 1} {:a 1.0})").unwrap(), Value::Boolean(true));
+    assert_eq!(
+        eval("(def-struct point x y) (set p (point)) (set q (point)) (set-member p x 1) (set-member q x 1.0) (= p q)")
+            .unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(def-struct a x) (def-struct b x) (= (a x 1) (b x 1))").unwrap(),
+        Value::Boolean(false)
+    );
+    assert_eq!(
+        eval("(def-struct a x) (def-struct b x) (= (list (a x 1)) (list (b x 1)))").unwrap(),
+        Value::Boolean(false)
+    );
+}
+
+#[test]
+fn identical_tests() {
+    assert_eq!(
+        eval("(def-struct point x) (set p (point)) (set q p) (identical? p q)").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(def-struct point x) (set p (point)) (set q (point)) (identical? p q)").unwrap(),
+        Value::Boolean(false)
+    );
+    assert_eq!(
+        eval("(def-struct point x) (set p (point)) (set q (point)) (= p q)").unwrap(),
+        Value::Boolean(true)
+    );
+
+    // plain values are identical when they are equal and of the same type
+    assert_eq!(eval("(identical? :a :a)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(identical? 1 1.0)").unwrap(), Value::Boolean(false));
+    assert_eq!(
+        eval("(identical? (list 1) (list 1))").unwrap(),
+        Value::Boolean(true)
+    );
+
+    // errors are only identical to the same error
+    assert_eq!(
+        eval("(set x (try (throw \"a\") (catch e e))) (identical? x x)").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(identical? (try (throw \"a\") (catch e e)) (try (throw \"a\") (catch e e)))")
+            .unwrap(),
+        Value::Boolean(false)
+    );
+}
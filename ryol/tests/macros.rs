@@ -0,0 +1,163 @@
+use ryol::prelude::*;
+
+fn symbol(name: &str) -> Value {
+    Value::Symbol(Symbol::new(name))
+}
+
+#[test]
+fn quote_tests() {
+    assert!(eval("(quote)").is_err());
+    assert!(eval("(quote a b)").is_err());
+
+    assert_eq!(eval("(quote a)").unwrap(), symbol("a"));
+    assert_eq!(eval("(quote 1)").unwrap(), Value::Integer(1));
+    assert_eq!(
+        eval("(quote \"a\")").unwrap(),
+        Value::String("a".to_string())
+    );
+    assert_eq!(eval("(quote ())").unwrap(), Value::List(Vec::new()));
+    assert_eq!(eval("(quote (f))").unwrap(), Value::List(vec![symbol("f")]));
+    assert_eq!(
+        eval("(quote (+ 1 x))").unwrap(),
+        Value::List(vec![symbol("+"), Value::Integer(1), symbol("x")])
+    );
+    assert_eq!(
+        eval("(quote ((a b) c))").unwrap(),
+        Value::List(vec![
+            Value::List(vec![symbol("a"), symbol("b")]),
+            symbol("c")
+        ])
+    );
+    assert_eq!(
+        eval("(format (quote (+ 1 (* 2 x))))").unwrap(),
+        Value::String("(list + 1 (list * 2 x))".to_string())
+    );
+}
+
+#[test]
+fn quasiquote_tests() {
+    assert_eq!(
+        eval("(set x 5) (quasiquote (+ 1 (unquote x)))").unwrap(),
+        Value::List(vec![symbol("+"), Value::Integer(1), Value::Integer(5)])
+    );
+    assert_eq!(
+        eval("(set x 5) (quasiquote (a (b (unquote (+ x 1)))))").unwrap(),
+        Value::List(vec![
+            symbol("a"),
+            Value::List(vec![symbol("b"), Value::Integer(6)])
+        ])
+    );
+    assert_eq!(
+        eval("(set xs (list 1 2)) (quasiquote (+ (unquote-splicing xs) 3))").unwrap(),
+        Value::List(vec![
+            symbol("+"),
+            Value::Integer(1),
+            Value::Integer(2),
+            Value::Integer(3)
+        ])
+    );
+    assert!(eval("(quasiquote (+ (unquote-splicing 1)))").is_err());
+    assert_eq!(eval("(quasiquote x)").unwrap(), symbol("x"));
+}
+
+#[test]
+fn defmacro_tests() {
+    assert!(eval("(defmacro)").is_err());
+    assert!(eval("(defmacro m)").is_err());
+
+    let source = r#"
+        (defmacro unless (condition & body)
+            (quasiquote (if (not (unquote condition)) (do (unquote-splicing body)))))
+        (set x 0)
+        (unless false (set x 1) (set x (+ x 1)))
+        (unless true (set x 10))
+        x
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(2));
+
+    // the arguments are not evaluated before being passed in
+    let source = r#"
+        (defmacro swap-args (call) (list (list-head call) (list-third call) (list-second call)))
+        (swap-args (- 1 10))
+    "#;
+    let mut run_state = RunState::new();
+    run_state
+        .expose_function("list-head", |args| list_nth(args, 0))
+        .unwrap();
+    run_state
+        .expose_function("list-second", |args| list_nth(args, 1))
+        .unwrap();
+    run_state
+        .expose_function("list-third", |args| list_nth(args, 2))
+        .unwrap();
+    assert_eq!(run_state.eval(source).unwrap(), Value::Integer(9));
+
+    // bad expansions are errors
+    assert!(eval("(defmacro m () (fn () 1)) (m)").is_err());
+}
+
+fn list_nth(args: Vec<Value>, n: usize) -> Result<Value, Error> {
+    match args.first() {
+        Some(Value::List(list)) => Ok(list[n].clone()),
+        _ => Err(Error::new("expected a list".to_string(), None)),
+    }
+}
+
+#[test]
+fn macroexpand_tests() {
+    let source = r#"
+        (defmacro inc (x) (quasiquote (set (unquote x) (+ (unquote x) 1))))
+        (macroexpand (quote (inc y)))
+    "#;
+    assert_eq!(
+        eval(source).unwrap(),
+        Value::List(vec![
+            symbol("set"),
+            symbol("y"),
+            Value::List(vec![symbol("+"), symbol("y"), Value::Integer(1)])
+        ])
+    );
+
+    // non macro forms are left alone
+    assert_eq!(
+        eval("(macroexpand (quote (+ 1 2)))").unwrap(),
+        eval("(quote (+ 1 2))").unwrap()
+    );
+    assert_eq!(eval("(macroexpand 1)").unwrap(), Value::Integer(1));
+}
+
+#[test]
+fn gensym_tests() {
+    assert!(matches!(eval("(gensym)").unwrap(), Value::Symbol(_)));
+    assert_eq!(
+        eval("(= (gensym) (gensym))").unwrap(),
+        Value::Boolean(false)
+    );
+    assert!(eval("(gensym 1)").is_err());
+
+    // gensym stops the expansion from capturing the callers variables
+    let source = r#"
+        (defmacro twice (expr)
+            (let (tmp (gensym "tmp"))
+                (quasiquote (let ((unquote tmp) (unquote expr)) (+ (unquote tmp) (unquote tmp))))))
+        (set tmp 5)
+        (twice (+ tmp 1))
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(12));
+}
+
+#[test]
+fn rest_parameter_tests() {
+    assert_eq!(
+        eval("(defn f (a & rest) rest) (f 1 2 3)").unwrap(),
+        Value::List(vec![Value::Integer(2), Value::Integer(3)])
+    );
+    assert_eq!(
+        eval("(defn f (a & rest) rest) (f 1)").unwrap(),
+        Value::List(Vec::new())
+    );
+    assert!(eval("(defn f (a & rest) rest) (f)").is_err());
+    assert!(eval("(fn (a &) a)").is_err());
+    assert!(eval("(fn (& a b) a)").is_err());
+    assert!(eval("(fn (a & a) a)").is_err());
+}
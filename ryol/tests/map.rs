@@ -0,0 +1,196 @@
+mod common;
+
+use common::string;
+use ryol::prelude::*;
+
+#[test]
+fn map_literal_syntax() {
+    assert!(eval("{}").is_ok());
+    assert!(eval("{\"a\" 1 \"b\" 2}").is_ok());
+    assert!(eval("{\"a\"}").is_err());
+    assert!(eval("{\"a\" 1").is_err());
+    assert!(eval("{\"a\" 1)").is_err());
+    assert!(eval("(list 1})").is_err());
+    assert!(eval("{(/ 0.0 (- 1e400 1e400)) 1}").is_err());
+    assert!(eval("{(fn (x) x) 1}").is_err());
+
+    assert_eq!(eval("(map-length {})").unwrap(), Value::Integer(0));
+    assert_eq!(
+        eval("(set k \"b\") {\"a\" (+ 1 2) k {1 true}}").unwrap(),
+        eval("(make-map \"a\" 3 \"b\" (make-map 1 true))").unwrap()
+    );
+
+    // the order of the keys does not matter for equality
+    assert_eq!(
+        eval("{\"a\" 1 \"b\" 2}").unwrap(),
+        eval("{\"b\" 2 \"a\" 1}").unwrap()
+    );
+    assert_ne!(
+        eval("{\"a\" 1 \"b\" 2}").unwrap(),
+        eval("{\"a\" 1 \"b\" 3}").unwrap()
+    );
+}
+
+#[test]
+fn map_access_tests() {
+    assert_eq!(
+        eval("(map-get {\"a\" 1} \"a\")").unwrap(),
+        Value::Integer(1)
+    );
+    assert_eq!(eval("(map-get {\"a\" 1} \"b\")").unwrap(), Value::Null);
+    assert_eq!(
+        eval("(map-get {\"a\" 1} \"b\" 2)").unwrap(),
+        Value::Integer(2)
+    );
+    assert_eq!(eval("(map-get {1 \"one\"} 1)").unwrap(), string("one"));
+    assert!(eval("(map-get (list) 1)").is_err());
+
+    assert_eq!(
+        eval("(map-has? {\"a\" 1} \"a\")").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(map-has? {\"a\" 1} \"b\")").unwrap(),
+        Value::Boolean(false)
+    );
+    assert_eq!(eval("(map? {})").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(map? (list))").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn map_update_tests() {
+    // maps are values so updating one gives back a new map
+    let source = r#"
+        (set m {"a" 1})
+        (set n (map-set m "b" 2 "a" 3))
+        (list (map-get m "a") (map-get n "a") (map-length n))
+    "#;
+    assert_eq!(
+        eval(source).unwrap(),
+        Value::List(vec![
+            Value::Integer(1),
+            Value::Integer(3),
+            Value::Integer(2)
+        ])
+    );
+
+    assert_eq!(
+        eval("(map-remove {\"a\" 1 \"b\" 2 \"c\" 3} \"a\" \"c\" \"d\")").unwrap(),
+        eval("{\"b\" 2}").unwrap()
+    );
+    assert!(eval("(map-set {} \"a\")").is_err());
+
+    assert_eq!(
+        eval("(map-merge {\"a\" 1 \"b\" 2} {\"b\" 3} {\"c\" 4})").unwrap(),
+        eval("{\"a\" 1 \"b\" 3 \"c\" 4}").unwrap()
+    );
+    assert!(eval("(map-merge {} (list))").is_err());
+}
+
+#[test]
+fn map_iteration_tests() {
+    let source = "(set m {\"b\" 2 \"a\" 1 \"c\" 3 \"a\" 4})";
+    assert_eq!(
+        eval(&format!("{} (map-keys m)", source)).unwrap(),
+        Value::List(vec![string("b"), string("a"), string("c")])
+    );
+    assert_eq!(
+        eval(&format!("{} (map-values m)", source)).unwrap(),
+        Value::List(vec![
+            Value::Integer(2),
+            Value::Integer(4),
+            Value::Integer(3)
+        ])
+    );
+    assert_eq!(
+        eval(&format!("{} (map-entries m)", source)).unwrap(),
+        eval("(list (list \"b\" 2) (list \"a\" 4) (list \"c\" 3))").unwrap()
+    );
+    assert_eq!(
+        eval(&format!(
+            "{} (set total 0) (for entry m (match entry ((list _ v) (set total (+ total v))))) total",
+            source
+        ))
+        .unwrap(),
+        Value::Integer(9)
+    );
+
+    assert_eq!(
+        format!("{}", eval("{\"a\" 1 \"b\" (list 2)}").unwrap()),
+        "{a 1 b (list 2)}"
+    );
+}
+
+#[test]
+fn compound_key_tests() {
+    assert_eq!(
+        eval("(map-get {(list 1 2) :a} (list 1 2))").unwrap(),
+        Value::Keyword(Symbol::new("a"))
+    );
+    assert_eq!(
+        eval("(map-get {1.5 :a} 1.5)").unwrap(),
+        Value::Keyword(Symbol::new("a"))
+    );
+    assert_eq!(
+        eval("(map-get {0.0 :a} -0.0)").unwrap(),
+        Value::Keyword(Symbol::new("a"))
+    );
+    assert_eq!(
+        eval("(map-get {{:x 1 :y 2} :a} {:y 2 :x 1})").unwrap(),
+        Value::Keyword(Symbol::new("a"))
+    );
+
+    // structures are keys by their contents
+    assert_eq!(
+        eval(
+            "(def-struct point x y)
+             (set p (point))
+             (set-member p x 1)
+             (set q (point))
+             (set-member q x 1)
+             (map-get (make-map p :a) q)"
+        )
+        .unwrap(),
+        Value::Keyword(Symbol::new("a"))
+    );
+
+    // keys are copied so changing the structure afterwards does not change them
+    assert_eq!(
+        eval(
+            "(def-struct point x)
+             (set p (point))
+             (set m (make-map p :a))
+             (set-member p x 1)
+             (list (map-has? m p) (map-keys m))"
+        )
+        .unwrap(),
+        eval(
+            "(def-struct point x)
+             (list false (list (point)))"
+        )
+        .unwrap()
+    );
+
+    // numbers with the same exact value are the same key, the first key is
+    // kept and the last value wins
+    assert_eq!(
+        eval("(map-length {1 :a 1.0 :b})").unwrap(),
+        Value::Integer(1)
+    );
+    assert_eq!(
+        eval("(map-get {1 :a 1.0 :b} 1)").unwrap(),
+        Value::Keyword(Symbol::new("b"))
+    );
+    assert_eq!(
+        eval("(map-get {(list 1/2) :a} (list 0.5))").unwrap(),
+        Value::Keyword(Symbol::new("a"))
+    );
+    assert_eq!(
+        eval("(map-get {{:x 1} :a} {:x 1.0})").unwrap(),
+        Value::Keyword(Symbol::new("a"))
+    );
+    assert_eq!(
+        eval("(map-keys {2.0 :a 2 :b})").unwrap(),
+        Value::List(vec![Value::Float(2.0)])
+    );
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::identity_op, clippy::erasing_op)]
+
 use ryol::prelude::*;
 
 #[test]
@@ -33,3 +35,258 @@ fn division_tests() {
     assert_eq!(eval("(/ 2 3)").unwrap(), Value::Integer(2 / 3));
     assert_eq!(eval("(/ 1 2 3)").unwrap(), Value::Integer(1 / 2 / 3));
 }
+
+#[test]
+fn mixed_type_tests() {
+    assert_eq!(eval("(+ 1 2.5)").unwrap(), Value::Float(3.5));
+    assert_eq!(eval("(+ 2.5 1)").unwrap(), Value::Float(3.5));
+    assert_eq!(eval("(- 1 0.5)").unwrap(), Value::Float(0.5));
+    assert_eq!(eval("(* 2 1.5)").unwrap(), Value::Float(3.0));
+    assert_eq!(eval("(/ 3 2.0)").unwrap(), Value::Float(1.5));
+    assert_eq!(eval("(+ 1 2 0.5)").unwrap(), Value::Float(3.5));
+    assert_eq!(eval("(+ 0.5 1 2)").unwrap(), Value::Float(3.5));
+
+    assert!(eval("(+ 1 \"a\")").is_err());
+    assert!(eval("(+ \"a\")").is_err());
+    assert!(eval("(* \"a\")").is_err());
+    assert!(eval("(- \"a\")").is_err());
+}
+
+#[test]
+fn single_argument_tests() {
+    assert_eq!(eval("(+ 2)").unwrap(), Value::Integer(2));
+    assert_eq!(eval("(- 2)").unwrap(), Value::Integer(-2));
+    assert_eq!(eval("(- 2.5)").unwrap(), Value::Float(-2.5));
+    assert_eq!(eval("(* 2)").unwrap(), Value::Integer(2));
+    assert_eq!(eval("(/ 4.0)").unwrap(), Value::Float(0.25));
+    assert_eq!(eval("(/ 1)").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(/ 2)").unwrap(), Value::Integer(1 / 2));
+}
+
+fn get_runtime_error(source: &str) -> Error {
+    match eval(source) {
+        Err(ryol::EvalError::RuntimeError(error)) => error,
+        res => panic!("expected a runtime error, recieved: {:?}", res),
+    }
+}
+
+fn big(integer: &str) -> Value {
+    Value::BigInteger(integer.parse().unwrap())
+}
+
+#[test]
+fn big_integer_tests() {
+    // integers grow instead of overflowing
+    assert_eq!(
+        eval("(+ 9223372036854775807 1)").unwrap(),
+        big("9223372036854775808")
+    );
+    assert_eq!(
+        eval("(- -9223372036854775807 2)").unwrap(),
+        big("-9223372036854775809")
+    );
+    assert_eq!(
+        eval("(* 9223372036854775807 9223372036854775807)").unwrap(),
+        big("85070591730234615847396907784232501249")
+    );
+    assert_eq!(
+        eval("(- (- -9223372036854775807 1))").unwrap(),
+        big("9223372036854775808")
+    );
+    assert_eq!(
+        eval("(/ (- -9223372036854775807 1) -1)").unwrap(),
+        big("9223372036854775808")
+    );
+
+    // and shrink back down when they fit again
+    assert_eq!(
+        eval("(- (+ 9223372036854775807 1) 1)").unwrap(),
+        Value::Integer(i64::MAX)
+    );
+    assert_eq!(
+        eval("(/ 100000000000000000000 100)").unwrap(),
+        Value::Integer(1000000000000000000)
+    );
+
+    assert_eq!(
+        eval("100000000000000000000").unwrap(),
+        big("100000000000000000000")
+    );
+    assert_eq!(
+        eval("(+ 100000000000000000000 0.5)").unwrap(),
+        Value::Float(1e20 + 0.5)
+    );
+    assert!(eval("(/ 100000000000000000000 0)").is_err());
+
+    assert_eq!(
+        format!("{}", eval("(* 100000000000000000000 3)").unwrap()),
+        "300000000000000000000"
+    );
+}
+
+#[test]
+fn big_integer_comparison_tests() {
+    assert_eq!(
+        eval("(= 100000000000000000000 (* 10000000000 10000000000))").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(< 1 100000000000000000000)").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(> -100000000000000000000 -1)").unwrap(),
+        Value::Boolean(false)
+    );
+    assert_eq!(
+        eval("(< 100000000000000000000 1e21)").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(= 100000000000000000000 1e20)").unwrap(),
+        Value::Boolean(true)
+    );
+}
+
+#[test]
+fn division_by_zero_tests() {
+    let error = get_runtime_error("(+ 1\n   (/ 1 0))");
+    assert!(error.get_message().contains("division by zero"));
+    assert_eq!(error.get_token().clone().unwrap().get_line_no(), 2);
+    assert_eq!(error.get_token().clone().unwrap().get_col_no(), 5);
+
+    assert!(eval("(/ 1.0 0)").is_err());
+    assert!(eval("(/ 1 0.0)").is_err());
+    assert!(eval("(/ 0)").is_err());
+    assert!(eval("(/ 4 2 0)").is_err());
+}
+
+#[test]
+fn integer_division_tests() {
+    assert_eq!(eval("(div 7 2)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(div -7 2)").unwrap(), Value::Integer(-4));
+    assert_eq!(eval("(mod 7 3)").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(mod -7 3)").unwrap(), Value::Integer(2));
+    assert_eq!(eval("(mod 7 -3)").unwrap(), Value::Integer(-2));
+    assert_eq!(eval("(rem -7 3)").unwrap(), Value::Integer(-1));
+    assert_eq!(eval("(mod 7.5 2)").unwrap(), Value::Float(1.5));
+    assert_eq!(eval("(mod -1.5 1)").unwrap(), Value::Float(0.5));
+    assert_eq!(eval("(div 7.5 2)").unwrap(), Value::Float(3.0));
+    assert_eq!(
+        eval("(mod 100000000000000000001 10)").unwrap(),
+        Value::Integer(1)
+    );
+
+    assert!(eval("(mod 1 0)").is_err());
+    assert!(eval("(div 1 0.0)").is_err());
+    assert!(eval("(rem 1 \"a\")").is_err());
+    assert!(eval("(mod 1)").is_err());
+}
+
+#[test]
+fn power_tests() {
+    assert_eq!(eval("(pow 2 10)").unwrap(), Value::Integer(1024));
+    assert_eq!(eval("(pow 2 0)").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(pow 2 64)").unwrap(), big("18446744073709551616"));
+    assert_eq!(eval("(pow 2 -2)").unwrap(), eval("1/4").unwrap());
+    assert_eq!(eval("(pow 2.0 3)").unwrap(), Value::Float(8.0));
+    assert_eq!(eval("(pow 4 0.5)").unwrap(), Value::Float(2.0));
+
+    assert!(eval("(pow 0 -1)").is_err());
+    assert!(eval("(pow -8 0.5)").is_err());
+    assert!(eval("(pow -8.0 1/3)").is_err());
+    assert_eq!(eval("(pow -2.0 3.0)").unwrap(), Value::Float(-8.0));
+
+    // exact results that would be too large are an error instead of hanging
+    assert!(eval("(pow 2 4000000000)").is_err());
+    assert!(eval("(pow 3/2 -4000000000)").is_err());
+    assert_eq!(eval("(pow 1 4000000000)").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(pow -1 4000000001)").unwrap(), Value::Integer(-1));
+    assert_eq!(
+        eval("(= (pow 2 100000) (* 2 (pow 2 99999)))").unwrap(),
+        Value::Boolean(true)
+    );
+    assert!(eval("(pow \"a\" 2)").is_err());
+
+    assert_eq!(eval("(sqrt 16)").unwrap(), Value::Float(4.0));
+    assert_eq!(eval("(exp 0)").unwrap(), Value::Float(1.0));
+    assert_eq!(eval("(ln 1)").unwrap(), Value::Float(0.0));
+    assert_eq!(eval("(log 1000)").unwrap(), Value::Float(3.0));
+    assert_eq!(eval("(log 8 2)").unwrap(), Value::Float(3.0));
+
+    assert!(eval("(sqrt -1)").is_err());
+    assert!(eval("(ln 0)").is_err());
+    assert!(eval("(log 8 1)").is_err());
+}
+
+#[test]
+fn trigonometry_tests() {
+    assert_eq!(eval("(sin 0)").unwrap(), Value::Float(0.0));
+    assert_eq!(eval("(cos 0)").unwrap(), Value::Float(1.0));
+    assert_eq!(eval("(tan 0.5)").unwrap(), Value::Float(0.5f64.tan()));
+    assert_eq!(eval("(asin 1)").unwrap(), Value::Float(1f64.asin()));
+    assert_eq!(eval("(acos 1)").unwrap(), Value::Float(0.0));
+    assert_eq!(eval("(atan 1)").unwrap(), Value::Float(1f64.atan()));
+    assert_eq!(eval("(atan 1 -1)").unwrap(), Value::Float(1f64.atan2(-1.0)));
+
+    assert!(eval("(asin 2)").is_err());
+    assert!(eval("(cos \"a\")").is_err());
+}
+
+#[test]
+fn rounding_tests() {
+    assert_eq!(eval("(abs -3)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(abs -3.5)").unwrap(), Value::Float(3.5));
+    assert_eq!(
+        eval("(abs (- -9223372036854775807 1))").unwrap(),
+        big("9223372036854775808")
+    );
+
+    assert_eq!(eval("(floor 2.5)").unwrap(), Value::Integer(2));
+    assert_eq!(eval("(floor -2.5)").unwrap(), Value::Integer(-3));
+    assert_eq!(eval("(ceil 2.1)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(round 2.5)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(round -2.5)").unwrap(), Value::Integer(-3));
+    assert_eq!(eval("(trunc -2.7)").unwrap(), Value::Integer(-2));
+    assert_eq!(eval("(floor 7/2)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(round 5)").unwrap(), Value::Integer(5));
+    assert_eq!(eval("(floor 1e20)").unwrap(), big("100000000000000000000"));
+
+    assert!(eval("(floor 1e400)").is_err());
+    assert!(eval("(round \"a\")").is_err());
+}
+
+#[test]
+fn min_max_tests() {
+    assert_eq!(eval("(min 3 1 2)").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(max 3 1 2)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(max 1 2.5)").unwrap(), Value::Float(2.5));
+    assert_eq!(eval("(min 1 1.0)").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(min 5)").unwrap(), Value::Integer(5));
+
+    assert_eq!(eval("(clamp 5 0 3)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(clamp -1 0 3)").unwrap(), Value::Integer(0));
+    assert_eq!(eval("(clamp 1.5 0 3)").unwrap(), Value::Float(1.5));
+
+    assert!(eval("(min)").is_err());
+    assert!(eval("(max 1 \"a\")").is_err());
+    assert!(eval("(clamp 1 3 0)").is_err());
+}
+
+#[test]
+fn constant_tests() {
+    assert_eq!(eval("pi").unwrap(), Value::Float(std::f64::consts::PI));
+    assert_eq!(eval("e").unwrap(), Value::Float(std::f64::consts::E));
+    assert_eq!(eval("euler").unwrap(), Value::Float(std::f64::consts::E));
+    assert_eq!(eval("tau").unwrap(), Value::Float(std::f64::consts::TAU));
+
+    // constants can not be changed, only shadowed
+    assert!(eval("(set pi 3)").is_err());
+    assert!(eval("(set e 5)").is_err());
+    assert!(eval("(set euler 5)").is_err());
+    assert_eq!(eval("(let (pi 3) pi)").unwrap(), Value::Integer(3));
+    assert_eq!(
+        eval("(let (pi 3) pi) pi").unwrap(),
+        Value::Float(std::f64::consts::PI)
+    );
+}
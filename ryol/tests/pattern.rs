@@ -0,0 +1,128 @@
+use ryol::prelude::*;
+
+#[test]
+fn match_syntax() {
+    assert!(eval("(match)").is_err());
+    assert!(eval("(match 1 (_))").is_ok());
+    assert!(eval("(match 1 (_ when))").is_err());
+    assert!(eval("(match 1 ((unknown x) 1))").is_err());
+    assert!(eval("(match (list 1) ((list & a b) 1))").is_err());
+}
+
+#[test]
+fn match_literal_tests() {
+    let source = r#"
+        (defn describe (x)
+            (match x
+                (0 "zero")
+                (1.5 "one and a half")
+                ("one" "string")
+                (true "true")
+                ((quote a) "symbol")
+                (_ "other")))
+        (list (describe 0) (describe 1.5) (describe "one") (describe true) (describe (quote a)) (describe 7))
+    "#;
+    assert_eq!(
+        eval(source).unwrap(),
+        Value::List(vec![
+            Value::String("zero".to_string()),
+            Value::String("one and a half".to_string()),
+            Value::String("string".to_string()),
+            Value::String("true".to_string()),
+            Value::String("symbol".to_string()),
+            Value::String("other".to_string()),
+        ])
+    );
+
+    // bindings are only visible inside of the clause
+    assert_eq!(eval("(match 5 (x (* x 2)))").unwrap(), Value::Integer(10));
+    assert!(eval("(match 5 (x x)) x").is_err());
+}
+
+#[test]
+fn match_list_tests() {
+    assert_eq!(
+        eval("(match (list 1 2 3) ((list a b) 0) ((list a b c) (+ a b c)))").unwrap(),
+        Value::Integer(6)
+    );
+    assert_eq!(
+        eval("(match (list 1 2 3) ((list first & rest) rest))").unwrap(),
+        Value::List(vec![Value::Integer(2), Value::Integer(3)])
+    );
+    assert_eq!(
+        eval("(match (list) ((list x & _) x) ((list) \"empty\"))").unwrap(),
+        Value::String("empty".to_string())
+    );
+    assert_eq!(
+        eval("(match (list 1 (list 2 3)) ((list 1 (list _ y)) y))").unwrap(),
+        Value::Integer(3)
+    );
+    assert_eq!(
+        eval("(match 1 ((list & _) \"list\") (_ \"not a list\"))").unwrap(),
+        Value::String("not a list".to_string())
+    );
+}
+
+#[test]
+fn match_structure_tests() {
+    let source = r#"
+        (def-struct vec2d x y)
+        (def-struct vec3d x y z)
+        (set v (vec2d))
+        (set-member v x 1)
+        (set-member v y 2)
+        (match v
+            ((vec3d x y z) "3d")
+            ((vec2d (x 0) y) "on the y axis")
+            ((vec2d x y) (+ x y)))
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(3));
+
+    assert!(eval("(def-struct vec2d x y) (match (vec2d) ((vec2d z) 0))").is_err());
+}
+
+#[test]
+fn match_guard_tests() {
+    let source = r#"
+        (defn sign (n)
+            (match n
+                (0 "zero")
+                (x when (< x 0) "negative")
+                (_ "positive")))
+        (list (sign -3) (sign 0) (sign 4))
+    "#;
+    assert_eq!(
+        eval(source).unwrap(),
+        Value::List(vec![
+            Value::String("negative".to_string()),
+            Value::String("zero".to_string()),
+            Value::String("positive".to_string()),
+        ])
+    );
+
+    assert!(eval("(match 1 (x when 1 x))").is_err());
+}
+
+#[test]
+fn match_non_exhaustive_tests() {
+    match eval("(match 3\n    (1 \"one\")\n    (2 \"two\"))") {
+        Err(ryol::EvalError::RuntimeError(error)) => {
+            let token = error.get_token().clone().unwrap();
+            assert_eq!(token.get_line_no(), 1);
+            assert_eq!(token.get_col_no(), 2);
+        }
+        res => panic!("expected a runtime error, recieved: {:?}", res),
+    }
+}
+
+#[test]
+fn match_tail_call_tests() {
+    let source = r#"
+        (defn sum (xs acc)
+            (match xs
+                ((list) acc)
+                ((list x & rest) (sum rest (+ acc x)))))
+        (sum (for i (range 1000) collect i) 0)
+    "#;
+    assert_eq!(eval(source).unwrap(), Value::Integer(499500));
+}
@@ -0,0 +1,121 @@
+use ryol::prelude::*;
+
+fn eval_seeded(seed: u64, source: &str) -> Value {
+    let mut run_state = RunState::new();
+    run_state.seed_random(seed);
+    run_state.eval(source).unwrap()
+}
+
+fn sorted(value: Value) -> Vec<i64> {
+    match value {
+        Value::List(list) => {
+            let mut integers: Vec<i64> = list
+                .into_iter()
+                .map(|value| match value {
+                    Value::Integer(integer) => integer,
+                    value => panic!("expected an integer, recieved: {:?}", value),
+                })
+                .collect();
+            integers.sort();
+            integers
+        }
+        value => panic!("expected a list, recieved: {:?}", value),
+    }
+}
+
+#[test]
+fn seeding_tests() {
+    let source = "(list (rand-int 1000000) (rand-float) (shuffle (list 1 2 3 4 5)))";
+
+    // the same seed always gives the same values
+    assert_eq!(eval_seeded(7, source), eval_seeded(7, source));
+    assert_ne!(eval_seeded(7, source), eval_seeded(8, source));
+
+    // seeding from the script is the same as seeding from the host
+    assert_eq!(
+        eval_seeded(1, &format!("(seed-random 7) {}", source)),
+        eval_seeded(7, source)
+    );
+
+    // each run state has its own generator
+    let mut x = RunState::new();
+    let mut y = RunState::new();
+    x.seed_random(3);
+    y.seed_random(3);
+    let x_first = x.eval("(rand-int 1000000)").unwrap();
+    x.eval("(rand-int 1000000)").unwrap();
+    assert_eq!(y.eval("(rand-int 1000000)").unwrap(), x_first);
+}
+
+#[test]
+fn range_tests() {
+    for seed in 0..20 {
+        match eval_seeded(seed, "(rand-int 3 6)") {
+            Value::Integer(integer) => assert!((3..6).contains(&integer)),
+            value => panic!("expected an integer, recieved: {:?}", value),
+        }
+        match eval_seeded(seed, "(rand-float -1 1)") {
+            Value::Float(float) => assert!((-1.0..1.0).contains(&float)),
+            value => panic!("expected a float, recieved: {:?}", value),
+        }
+    }
+
+    assert_eq!(eval_seeded(0, "(rand-int 1)"), Value::Integer(0));
+    assert!(eval("(rand-int 0)").is_err());
+    assert!(eval("(rand-int 5 5)").is_err());
+    assert!(eval("(rand-int 1.5)").is_err());
+    assert!(eval("(rand-float 1 0)").is_err());
+    assert!(eval("(rand-float \"a\")").is_err());
+}
+
+#[test]
+fn collection_tests() {
+    assert_eq!(
+        sorted(eval_seeded(5, "(shuffle (list 5 3 1 4 2))")),
+        vec![1, 2, 3, 4, 5]
+    );
+    assert_eq!(
+        sorted(eval_seeded(5, "(shuffle (range 5))")),
+        vec![0, 1, 2, 3, 4]
+    );
+    assert_eq!(eval_seeded(5, "(shuffle (list))"), Value::List(vec![]));
+
+    assert_eq!(eval_seeded(5, "(choice (list 4))"), Value::Integer(4));
+    match eval_seeded(5, "(choice (list 1 2 3))") {
+        Value::Integer(integer) => assert!((1..=3).contains(&integer)),
+        value => panic!("expected an integer, recieved: {:?}", value),
+    }
+
+    let sample = sorted(eval_seeded(5, "(sample (range 10) 4)"));
+    assert_eq!(sample.len(), 4);
+    assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(
+        sorted(eval_seeded(5, "(sample (list 1 2 3) 3)")),
+        vec![1, 2, 3]
+    );
+
+    assert!(eval("(choice (list))").is_err());
+    assert!(eval("(sample (list 1 2) 3)").is_err());
+    assert!(eval("(sample (list 1 2) -1)").is_err());
+    assert!(eval("(shuffle 5)").is_err());
+}
+
+#[test]
+fn random_function_value_tests() {
+    // the random functions are values like any other function
+    assert_eq!(
+        eval_seeded(7, "(set f rand-int) (f 1000000)"),
+        eval_seeded(7, "(rand-int 1000000)")
+    );
+    assert_eq!(
+        eval_seeded(7, "(set f rand-int) (list (f 10) (f 10))"),
+        eval_seeded(7, "(list (rand-int 10) (rand-int 10))")
+    );
+    assert_eq!(
+        eval_seeded(7, "(map rand-int (list 10 100))"),
+        eval_seeded(7, "(list (rand-int 10) (rand-int 100))")
+    );
+
+    let mut run_state = RunState::new();
+    assert!(run_state.eval("(set f rand-int) (f)").is_err());
+}
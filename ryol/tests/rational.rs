@@ -0,0 +1,87 @@
+use ryol::prelude::*;
+
+fn rational(source: &str) -> Value {
+    eval(&format!("(rational {})", source)).unwrap()
+}
+
+#[test]
+fn rational_literal_tests() {
+    assert_eq!(eval("1/3").unwrap(), rational("1 3"));
+    assert_eq!(eval("2/6").unwrap(), rational("1 3"));
+    assert_eq!(eval("-1/3").unwrap(), rational("-1 3"));
+    assert_eq!(eval("4/2").unwrap(), Value::Integer(2));
+    assert!(eval("1/0").is_err());
+
+    assert_eq!(format!("{}", eval("2/6").unwrap()), "1/3");
+    assert_eq!(format!("{}", eval("(rational 1 -3)").unwrap()), "-1/3");
+}
+
+#[test]
+fn rational_constructor_tests() {
+    assert_eq!(eval("(rational 6 3)").unwrap(), Value::Integer(2));
+    assert_eq!(eval("(rational 5)").unwrap(), Value::Integer(5));
+    assert_eq!(eval("(rational 0.5)").unwrap(), eval("1/2").unwrap());
+    assert_eq!(eval("(rational 1/2 3)").unwrap(), eval("1/6").unwrap());
+    assert!(eval("(rational 1 0)").is_err());
+    assert!(eval("(rational \"1\")").is_err());
+    assert!(eval("(rational 1e400)").is_err());
+
+    assert_eq!(eval("(rational? 1/2)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(rational? 1)").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn rational_arithmetic_tests() {
+    assert_eq!(eval("(+ 1/3 1/6)").unwrap(), eval("1/2").unwrap());
+    assert_eq!(eval("(+ 1/3 2/3)").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(- 1/2 1)").unwrap(), eval("-1/2").unwrap());
+    assert_eq!(eval("(* 2/3 3)").unwrap(), Value::Integer(2));
+    assert_eq!(eval("(/ 1 1/3)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(/ 1/3 2)").unwrap(), eval("1/6").unwrap());
+    assert_eq!(eval("(/ 1/2)").unwrap(), Value::Integer(2));
+    assert_eq!(eval("(+ 1/2 0.25)").unwrap(), Value::Float(0.75));
+    assert_eq!(
+        eval("(* 1/2 100000000000000000000)").unwrap(),
+        Value::BigInteger("50000000000000000000".parse().unwrap())
+    );
+    assert!(eval("(/ 1/2 0)").is_err());
+
+    // integer division is still truncated unless asked for
+    assert_eq!(eval("(/ 3 2)").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(/ (rational 3) 2)").unwrap(), Value::Integer(1));
+    assert_eq!(eval("(/ (rational 3 1) 2)").unwrap(), Value::Integer(1));
+}
+
+#[test]
+fn rational_comparison_tests() {
+    assert_eq!(eval("(< 1/3 1/2)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(< 1/3 0)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(= 1/2 0.5)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(= 1/2 2/4)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(> 1/2 0.4)").unwrap(), Value::Boolean(true));
+    assert_eq!(
+        eval("(map-get {1/2 :half} 2/4)").unwrap(),
+        eval(":half").unwrap()
+    );
+}
+
+#[test]
+fn rational_conversion_tests() {
+    assert_eq!(eval("(numerator 6/4)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(denominator 6/4)").unwrap(), Value::Integer(2));
+    assert_eq!(eval("(numerator -1/2)").unwrap(), Value::Integer(-1));
+    assert_eq!(eval("(denominator 5)").unwrap(), Value::Integer(1));
+    assert!(eval("(numerator 0.5)").is_err());
+
+    assert_eq!(eval("(to-float 1/4)").unwrap(), Value::Float(0.25));
+    assert_eq!(eval("(to-float 2)").unwrap(), Value::Float(2.0));
+    assert_eq!(eval("(to-integer 7/2)").unwrap(), Value::Integer(3));
+    assert_eq!(eval("(to-integer -7/2)").unwrap(), Value::Integer(-3));
+    assert_eq!(eval("(to-integer 2.9)").unwrap(), Value::Integer(2));
+    assert_eq!(
+        eval("(to-integer 1e20)").unwrap(),
+        Value::BigInteger("100000000000000000000".parse().unwrap())
+    );
+    assert!(eval("(to-integer 1e400)").is_err());
+    assert!(eval("(to-integer \"1\")").is_err());
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{string, strings};
+use ryol::prelude::*;
+
+#[test]
+fn match_tests() {
+    assert_eq!(
+        eval("(re-match? \"\\\\d+\" \"abc 123\")").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(re-match? \"^\\\\d+$\" \"abc 123\")").unwrap(),
+        Value::Boolean(false)
+    );
+
+    assert!(eval("(re-match? \"(\" \"abc\")").is_err());
+    assert!(eval("(re-match? 1 \"abc\")").is_err());
+    assert!(eval("(re-match? \"a\" 1)").is_err());
+    assert!(eval("(re-match? \"a\")").is_err());
+}
+
+#[test]
+fn find_tests() {
+    assert_eq!(
+        eval("(re-find \"\\\\d+\" \"a 12 b 345\")").unwrap(),
+        string("12")
+    );
+    assert_eq!(eval("(re-find \"\\\\d+\" \"abc\")").unwrap(), Value::Null);
+
+    // groups come back as a list after the whole match
+    assert_eq!(
+        eval("(re-find \"(\\\\w+)=(\\\\d+)\" \"x=1 y=2\")").unwrap(),
+        strings(&["x=1", "x", "1"])
+    );
+    assert_eq!(
+        eval("(re-find \"a(b)?\" \"a\")").unwrap(),
+        Value::List(vec![string("a"), Value::Null])
+    );
+
+    assert_eq!(
+        eval("(re-find-all \"\\\\d+\" \"a 12 b 345\")").unwrap(),
+        strings(&["12", "345"])
+    );
+    assert_eq!(
+        eval("(re-find-all \"(\\\\w)=(\\\\d)\" \"x=1 y=2\")").unwrap(),
+        Value::List(vec![
+            strings(&["x=1", "x", "1"]),
+            strings(&["y=2", "y", "2"])
+        ])
+    );
+    assert_eq!(
+        eval("(re-find-all \"\\\\d\" \"abc\")").unwrap(),
+        Value::List(vec![])
+    );
+}
+
+#[test]
+fn replace_split_tests() {
+    assert_eq!(
+        eval("(re-replace \"(\\\\w+)@(\\\\w+)\" \"me@home you@work\" \"$2:$1\")").unwrap(),
+        string("home:me work:you")
+    );
+    assert_eq!(
+        eval("(re-replace \"(?P<n>\\\\d)\" \"a1b2\" \"<${n}>\")").unwrap(),
+        string("a<1>b<2>")
+    );
+
+    assert_eq!(
+        eval("(re-split \"\\\\s*,\\\\s*\" \"a , b,c\")").unwrap(),
+        strings(&["a", "b", "c"])
+    );
+}
+
+#[test]
+fn cache_tests() {
+    // the same pattern in a loop is only compiled once
+    let mut run_state = RunState::new();
+    assert_eq!(
+        run_state
+            .eval("(for i (range 3) collect (re-find \"\\\\d\" (format \"a\" i)))")
+            .unwrap(),
+        strings(&["0", "1", "2"])
+    );
+    assert!(run_state.get_regex("\\d").is_ok());
+    assert!(run_state.get_regex("(").is_err());
+
+    match run_state.eval("(do\n  (re-find \"(\" \"a\"))") {
+        Err(ryol::EvalError::RuntimeError(error)) => {
+            assert!(error.get_message().contains("invalid pattern"));
+            assert_eq!(error.get_token().clone().unwrap().get_line_no(), 2);
+        }
+        res => panic!("expected a runtime error, recieved: {:?}", res),
+    }
+}
+
+#[test]
+fn regex_function_value_tests() {
+    assert_eq!(
+        eval("(set f re-find) (f \"\\\\d+\" \"a 12\")").unwrap(),
+        string("12")
+    );
+    assert_eq!(
+        eval("(map re-match? (list \"a\" \"b\") (list \"abc\" \"xyz\"))").unwrap(),
+        Value::List(vec![Value::Boolean(true), Value::Boolean(false)])
+    );
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{boolean, display};
+use ryol::prelude::*;
+
+#[test]
+fn set_basic_tests() {
+    assert_eq!(display("(make-set 3 1 2 1)"), "(make-set 1 2 3)");
+    assert_eq!(display("(make-set)"), "(make-set)");
+    assert_eq!(
+        display("(to-set (list :b :a :b (list 1) (list 1)))"),
+        "(make-set :a :b (list 1))"
+    );
+    assert_eq!(display("(to-set \"abca\")"), "(make-set a b c)");
+    assert_eq!(
+        eval("(length (make-set 1 1 2))").unwrap(),
+        Value::Integer(2)
+    );
+
+    assert!(boolean("(set? (make-set))"));
+    assert!(!boolean("(set? (list))"));
+    assert!(boolean("(member? (make-set 1 2) 2)"));
+    assert!(boolean("(member? (make-set 1 2) 2.0)"));
+    assert!(!boolean("(member? (make-set 1 2) (fn (x) x))"));
+
+    assert_eq!(display("(add (make-set 1) 2 1)"), "(make-set 1 2)");
+    assert_eq!(display("(remove (make-set 1 2 3) 2 4)"), "(make-set 1 3)");
+
+    assert!(eval("(make-set (/ 0.0 (- 1e400 1e400)))").is_err());
+    assert!(eval("(add (list) 1)").is_err());
+    assert!(eval("(member? (make-set))").is_err());
+}
+
+#[test]
+fn set_operation_tests() {
+    assert_eq!(
+        display("(union (make-set 1 2) (make-set 2 3) (make-set 4))"),
+        "(make-set 1 2 3 4)"
+    );
+    assert_eq!(
+        display("(intersection (make-set 1 2 3) (make-set 2 3 4))"),
+        "(make-set 2 3)"
+    );
+    assert_eq!(
+        display("(difference (make-set 1 2 3) (make-set 2))"),
+        "(make-set 1 3)"
+    );
+    assert!(boolean("(subset? (make-set 1) (make-set 1 2))"));
+    assert!(!boolean("(subset? (make-set 1 3) (make-set 1 2))"));
+    assert!(boolean("(subset? (make-set) (make-set))"));
+
+    assert!(eval("(union (make-set) (list))").is_err());
+}
+
+#[test]
+fn set_equality_tests() {
+    // the order elements are added in does not matter
+    assert!(boolean("(= (make-set 1 2 3) (make-set 3 2 1))"));
+    assert!(!boolean("(= (make-set 1 2) (make-set 1))"));
+    assert_eq!(
+        display("(make-set (make-set 2 1) (make-set 1 2))"),
+        "(make-set (make-set 1 2))"
+    );
+
+    assert_eq!(
+        eval("(for x (make-set 3 1 2) collect x)").unwrap(),
+        eval("(list 3 1 2)").unwrap()
+    );
+    assert_eq!(
+        eval("(sort (list (make-set 2) (make-set 1 2) (make-set 1)))").unwrap(),
+        eval("(list (make-set 1) (make-set 1 2) (make-set 2))").unwrap()
+    );
+}
+
+#[test]
+fn numeric_element_tests() {
+    // numbers with the same exact value are the same element, as with "="
+    assert!(boolean("(member? (make-set 1) 1.0)"));
+    assert!(boolean("(member? (make-set 0.5) 1/2)"));
+    assert!(boolean("(member? (make-set (list 1 2)) (list 1.0 2))"));
+    assert_eq!(display("(make-set 1 1.0 2)"), "(make-set 1 2)");
+    assert!(boolean("(= (make-set 1) (make-set 1.0))"));
+
+    // floats are compared by their exact value, so a float is not the same
+    // element as a number it only equals after rounding, as with "="
+    assert!(!boolean("(= 0.1 1/10)"));
+    assert!(!boolean("(member? (make-set 0.1) 1/10)"));
+    assert!(!boolean("(= 9007199254740993 9007199254740992.0)"));
+    assert_eq!(
+        eval("(length (to-set (list 9007199254740993 9007199254740992.0)))").unwrap(),
+        Value::Integer(2)
+    );
+}
+
+#[test]
+fn hash_tests() {
+    assert!(boolean("(= (hash (list 1 :a)) (hash (list 1 :a)))"));
+    assert!(boolean("(= (hash {:a 1 :b 2}) (hash {:b 2 :a 1}))"));
+    assert!(boolean("(= (hash 0.0) (hash -0.0))"));
+    assert!(boolean("(= (hash 1) (hash 1.0))"));
+    assert!(boolean("(= (hash (list 1/2)) (hash (list 0.5)))"));
+    assert!(eval("(hash (fn (x) x))").is_err());
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::integers;
+use ryol::prelude::*;
+
+#[test]
+fn sort_tests() {
+    assert_eq!(eval("(sort (list 3 1 2))").unwrap(), integers(&[1, 2, 3]));
+    assert_eq!(eval("(sort (list))").unwrap(), integers(&[]));
+    assert_eq!(eval("(sort (range 3 0 -1))").unwrap(), integers(&[1, 2, 3]));
+
+    // numbers are compared by value whatever their type
+    assert_eq!(
+        eval("(sort (list 2.5 1 1/2 100000000000000000000 -1e400))").unwrap(),
+        eval("(list -1e400 1/2 1 2.5 100000000000000000000)").unwrap()
+    );
+    assert_eq!(
+        eval("(sort (list \"b\" \"a\" \"ab\"))").unwrap(),
+        eval("(list \"a\" \"ab\" \"b\")").unwrap()
+    );
+    assert_eq!(
+        eval("(sort (list (list 1 2) (list 1) (list 0 5)))").unwrap(),
+        eval("(list (list 0 5) (list 1) (list 1 2))").unwrap()
+    );
+
+    // different types are ordered by type
+    assert_eq!(
+        eval("(sort (list :a \"a\" 1 (list) (first (list)) true (quote a)))").unwrap(),
+        eval("(list (first (list)) true 1 \"a\" (quote a) :a (list))").unwrap()
+    );
+
+    assert!(eval("(sort 5)").is_err());
+}
+
+#[test]
+fn stable_sort_tests() {
+    // equal numbers of different types keep their order
+    assert_eq!(
+        eval("(sort (list 2 1.0 1 2.0))").unwrap(),
+        eval("(list 1.0 1 2 2.0)").unwrap()
+    );
+    assert_eq!(
+        eval("(sort-by first (list (list 1 :a) (list 0 :b) (list 1 :c) (list 0 :d)))").unwrap(),
+        eval("(list (list 0 :b) (list 0 :d) (list 1 :a) (list 1 :c))").unwrap()
+    );
+}
+
+#[test]
+fn sort_by_tests() {
+    assert_eq!(
+        eval("(sort-by length (list \"ccc\" \"a\" \"bb\"))").unwrap(),
+        eval("(list \"a\" \"bb\" \"ccc\")").unwrap()
+    );
+    assert_eq!(
+        eval("(sort-by - (list 1 3 2))").unwrap(),
+        integers(&[3, 2, 1])
+    );
+    assert_eq!(
+        eval("(sort-with (fn (x y) (- y x)) (list 1 3 2))").unwrap(),
+        integers(&[3, 2, 1])
+    );
+
+    assert!(eval("(sort-with (fn (x y) true) (list 1 2))").is_err());
+    assert!(eval("(sort-by 5 (list 1 2))").is_err());
+
+    // a comparator that is not consistent still gives back every element
+    match eval("(sort-with (fn (x y) 1) (list 1 2 3 4 5))").unwrap() {
+        Value::List(list) => assert_eq!(list.len(), 5),
+        value => panic!("expected a list, recieved: {:?}", value),
+    }
+}
+
+#[test]
+fn min_max_by_tests() {
+    assert_eq!(
+        eval("(min-by length (list \"bb\" \"a\" \"c\"))").unwrap(),
+        Value::String("a".to_string())
+    );
+    assert_eq!(
+        eval("(max-by length (list \"bb\" \"a\" \"cc\"))").unwrap(),
+        Value::String("bb".to_string())
+    );
+    assert!(eval("(min-by length (list))").is_err());
+
+    // the higher order sorts are values themselves
+    assert_eq!(
+        eval("(map min-by (list length -) (list (list \"bb\" \"a\") (list 1 2)))").unwrap(),
+        Value::List(vec![Value::String("a".to_string()), Value::Integer(2)])
+    );
+}
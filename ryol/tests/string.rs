@@ -1,3 +1,6 @@
+mod common;
+
+use common::{string, strings};
 use ryol::prelude::*;
 
 #[test]
@@ -59,3 +62,210 @@ fn format_combined_tests() {
         Value::String("asdf".to_string())
     );
 }
+
+#[test]
+fn length_and_indexing_tests() {
+    assert_eq!(eval("(length \"hello\")").unwrap(), Value::Integer(5));
+    assert_eq!(eval("(length \"\")").unwrap(), Value::Integer(0));
+    assert_eq!(eval("(length \"héllo→\")").unwrap(), Value::Integer(6));
+
+    assert_eq!(eval("(substring \"héllo\" 1 3)").unwrap(), string("él"));
+    assert_eq!(eval("(substring \"héllo\" 2)").unwrap(), string("llo"));
+    assert_eq!(eval("(substring \"héllo\" 5)").unwrap(), string(""));
+    assert_eq!(eval("(slice \"héllo\" -3)").unwrap(), string("llo"));
+    assert_eq!(eval("(slice \"héllo\" 0 -1)").unwrap(), string("héll"));
+
+    assert!(eval("(substring \"abc\" 4)").is_err());
+    assert!(eval("(substring \"abc\" -1)").is_err());
+    assert!(eval("(substring \"abc\" 2 1)").is_err());
+    assert!(eval("(slice \"abc\" -4)").is_err());
+    assert!(eval("(substring 5 1)").is_err());
+
+    assert_eq!(
+        eval("(index-of \"→ab→c\" \"c\")").unwrap(),
+        Value::Integer(4)
+    );
+    assert_eq!(eval("(index-of \"abc\" \"d\")").unwrap(), Value::Null);
+
+    assert_eq!(eval("(chars \"hé\")").unwrap(), strings(&["h", "é"]));
+    assert_eq!(eval("(reverse \"héllo\")").unwrap(), string("olléh"));
+}
+
+#[test]
+fn split_join_tests() {
+    assert_eq!(
+        eval("(split \"a,b,,c\" \",\")").unwrap(),
+        strings(&["a", "b", "", "c"])
+    );
+    assert_eq!(
+        eval("(split \"  a b\\tc \")").unwrap(),
+        strings(&["a", "b", "c"])
+    );
+    assert!(eval("(split \"abc\" \"\")").is_err());
+
+    assert_eq!(
+        eval("(join (list \"a\" \"b\" \"c\") \", \")").unwrap(),
+        string("a, b, c")
+    );
+    assert_eq!(eval("(join (list \"a\" \"b\"))").unwrap(), string("ab"));
+    assert_eq!(eval("(join (list))").unwrap(), string(""));
+    assert!(eval("(join (list \"a\" 1))").is_err());
+    assert!(eval("(join \"abc\")").is_err());
+
+    assert_eq!(
+        eval("(replace \"a-b-c\" \"-\" \"+\")").unwrap(),
+        string("a+b+c")
+    );
+    assert!(eval("(replace \"abc\" \"\" \"x\")").is_err());
+
+    assert_eq!(eval("(repeat \"ab\" 3)").unwrap(), string("ababab"));
+    assert_eq!(eval("(repeat \"ab\" 0)").unwrap(), string(""));
+    assert!(eval("(repeat \"ab\" -1)").is_err());
+    // too large a result is an error rather than running out of memory
+    assert!(eval("(repeat \"ab\" 9223372036854775807)").is_err());
+    assert!(eval("(repeat \"ab\" 1000000000)").is_err());
+    assert_eq!(
+        eval("(repeat \"\" 9223372036854775807)").unwrap(),
+        string("")
+    );
+}
+
+#[test]
+fn predicate_tests() {
+    assert_eq!(
+        eval("(starts-with? \"hello\" \"he\")").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("(ends-with? \"hello\" \"he\")").unwrap(),
+        Value::Boolean(false)
+    );
+    assert_eq!(
+        eval("(contains? \"hello\" \"ll\")").unwrap(),
+        Value::Boolean(true)
+    );
+    assert!(eval("(contains? \"hello\" 1)").is_err());
+}
+
+#[test]
+fn case_and_whitespace_tests() {
+    assert_eq!(eval("(upper \"héllo\")").unwrap(), string("HÉLLO"));
+    assert_eq!(eval("(lower \"HÉLLO\")").unwrap(), string("héllo"));
+    assert_eq!(eval("(trim \"  a b \")").unwrap(), string("a b"));
+    assert_eq!(eval("(trim-left \"  a \")").unwrap(), string("a "));
+    assert_eq!(eval("(trim-right \"  a \")").unwrap(), string("  a"));
+    assert!(eval("(upper 1)").is_err());
+
+    assert_eq!(eval("(pad-left \"é\" 3)").unwrap(), string("  é"));
+    assert_eq!(eval("(pad-right \"7\" 3 \"0\")").unwrap(), string("700"));
+    assert_eq!(eval("(pad-left \"abcd\" 2)").unwrap(), string("abcd"));
+    assert!(eval("(pad-left \"a\" 3 \"xy\")").is_err());
+    assert!(eval("(pad-left \"a\" 9223372036854775807)").is_err());
+    assert!(eval("(pad-right \"a\" 9223372036854775807 \"é\")").is_err());
+}
+
+#[test]
+fn format_template_tests() {
+    assert_eq!(
+        eval("(fmt \"{} + {} = {}\" 1 2 3)").unwrap(),
+        string("1 + 2 = 3")
+    );
+    assert_eq!(
+        eval("(fmt \"{1} {0} {1}\" \"a\" \"b\")").unwrap(),
+        string("b a b")
+    );
+    assert_eq!(eval("(fmt \"{{{}}}\" 1)").unwrap(), string("{1}"));
+
+    assert_eq!(
+        eval("(fmt \"no placeholders\")").unwrap(),
+        string("no placeholders")
+    );
+
+    // format only ever joins its arguments together, braces and all
+    assert_eq!(eval("(format \"{}\")").unwrap(), string("{}"));
+    assert_eq!(
+        eval("(format \"set: {1 2} \" 5)").unwrap(),
+        string("set: {1 2} 5")
+    );
+    assert_eq!(eval("(format \"{}\" 1 2 3)").unwrap(), string("{}123"));
+
+    // every argument must have a placeholder and every placeholder an argument
+    assert!(eval("(fmt \"{} {}\" 1)").is_err());
+    assert!(eval("(fmt \"{}\" 1 2 3)").is_err());
+    assert!(eval("(fmt \"{1}\" 1 2)").is_err());
+    assert!(eval("(fmt \"{}\")").is_err());
+    assert!(eval("(fmt 1)").is_err());
+    assert!(eval("(fmt)").is_err());
+
+    // printf takes the same templates
+    assert_eq!(eval("(printf \"\")").unwrap(), Value::Null);
+    assert!(eval("(printf \"{}\" 1 2)").is_err());
+
+    // as do print and println when the first argument has a brace in it,
+    // otherwise they join their arguments like format
+    assert_eq!(eval("(println \"{}\" 1)").unwrap(), Value::Null);
+    assert!(eval("(println \"{}\" 1 2)").is_err());
+    assert!(eval("(print \"{:q}\" 1)").is_err());
+    assert_eq!(eval("(println \"a\" 1 2)").unwrap(), Value::Null);
+    assert_eq!(eval("(print 1 \"{}\")").unwrap(), Value::Null);
+    assert!(eval("(fmt \"{\" 1)").is_err());
+    assert!(eval("(fmt \"}\" 1)").is_err());
+    assert!(eval("(fmt \"{:q}\" 1)").is_err());
+}
+
+#[test]
+fn format_spec_tests() {
+    assert_eq!(eval("(fmt \"{:.2}\" 3.14159)").unwrap(), string("3.14"));
+    assert_eq!(eval("(fmt \"{:.1}\" 2)").unwrap(), string("2.0"));
+    assert_eq!(eval("(fmt \"{:.3}\" 1/3)").unwrap(), string("0.333"));
+    assert_eq!(eval("(fmt \"{:.2}\" \"abc\")").unwrap(), string("ab"));
+
+    assert_eq!(eval("(fmt \"[{:5}]\" 42)").unwrap(), string("[   42]"));
+    assert_eq!(eval("(fmt \"[{:5}]\" \"ab\")").unwrap(), string("[ab   ]"));
+    assert_eq!(eval("(fmt \"[{:>5}]\" \"ab\")").unwrap(), string("[   ab]"));
+    assert_eq!(
+        eval("(fmt \"[{:*^6}]\" \"ab\")").unwrap(),
+        string("[**ab**]")
+    );
+    assert_eq!(eval("(fmt \"[{:-<4}]\" 1)").unwrap(), string("[1---]"));
+    assert_eq!(eval("(fmt \"[{:3}]\" \"→\")").unwrap(), string("[→  ]"));
+
+    assert_eq!(eval("(fmt \"{:+}\" 5)").unwrap(), string("+5"));
+    assert_eq!(eval("(fmt \"{:+}\" -5)").unwrap(), string("-5"));
+    assert_eq!(eval("(fmt \"{:05}\" -42)").unwrap(), string("-0042"));
+    assert_eq!(
+        eval("(fmt \"{:+08.2}\" 3.14159)").unwrap(),
+        string("+0003.14")
+    );
+
+    assert_eq!(eval("(fmt \"{:x}\" 255)").unwrap(), string("ff"));
+    assert_eq!(eval("(fmt \"{:#X}\" 255)").unwrap(), string("0xFF"));
+    assert_eq!(eval("(fmt \"{:o}\" 8)").unwrap(), string("10"));
+    assert_eq!(eval("(fmt \"{:#010b}\" 5)").unwrap(), string("0b00000101"));
+    assert_eq!(eval("(fmt \"{:x}\" -255)").unwrap(), string("-ff"));
+    assert_eq!(
+        eval("(fmt \"{:x}\" 18446744073709551616)").unwrap(),
+        string("10000000000000000")
+    );
+    assert!(eval("(fmt \"{:x}\" 1.5)").is_err());
+    assert!(eval("(fmt \"{:b}\" \"a\")").is_err());
+}
+
+#[test]
+fn format_named_tests() {
+    assert_eq!(
+        eval(
+            "(def-struct point x y)
+             (set p (point))
+             (set-member p x 1)
+             (set-member p y 2.5)
+             (fmt \"({x}, {y:.2})\" p)"
+        )
+        .unwrap(),
+        string("(1, 2.50)")
+    );
+
+    assert!(eval("(def-struct point x) (fmt \"{z}\" (point))").is_err());
+    assert!(eval("(def-struct point x) (fmt \"{x}\" (point) 1)").is_err());
+    assert!(eval("(fmt \"{x}\" 1)").is_err());
+}
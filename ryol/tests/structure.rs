@@ -12,6 +12,7 @@ fn structure_definition_syntax() {
 #[test]
 fn structure_standard_usage() {
     let mut vec2d_template = StructureTemplate::new();
+    vec2d_template.set_identifier("vec2d");
     vec2d_template.add_member(&"x".to_string()).unwrap();
     vec2d_template.add_member(&"y".to_string()).unwrap();
     let vec2d = StructureInstance::from_template(&vec2d_template);
@@ -43,3 +44,79 @@ fn structure_standard_usage() {
         Value::Integer(3)
     );
 }
+
+#[test]
+fn structure_default_members() {
+    let mut point_template = StructureTemplate::new();
+    point_template.set_identifier("point");
+    point_template
+        .add_member_with_default(&"x".to_string(), Value::Integer(0))
+        .unwrap();
+    point_template
+        .add_member_with_default(&"y".to_string(), Value::Integer(0))
+        .unwrap();
+    let point = StructureInstance::from_template(&point_template);
+
+    assert_eq!(
+        eval("(def-struct point (x 0) (y 0)) (point)").unwrap(),
+        Value::Structure(point)
+    );
+    assert_eq!(
+        eval("(def-struct point x (y (+ 1 2))) (set p (point)) (get-member p y)").unwrap(),
+        Value::Integer(3)
+    );
+    assert_eq!(
+        eval("(def-struct point x (y 0)) (set p (point)) (get-member p x)").unwrap(),
+        Value::default()
+    );
+
+    // each instance gets its own copy of a default structure
+    assert_eq!(
+        eval("(def-struct inner v) (def-struct outer (i (inner))) (set a (outer)) (set b (outer)) (set c (get-member a i)) (set-member c v 1) (set d (get-member b i)) (get-member d v)").unwrap(),
+        Value::default()
+    );
+
+    // and of a default collection, even inside of a list
+    assert_eq!(
+        eval("(def-struct q (items (make-deque))) (set a (q)) (set b (q)) (push-back (get-member a items) 1) (length (get-member b items))").unwrap(),
+        Value::Integer(0)
+    );
+    assert_eq!(
+        eval("(def-struct q (items (list (make-heap)))) (set a (q)) (set b (q)) (heap-push (first (get-member a items)) 1) (length (first (get-member b items)))").unwrap(),
+        Value::Integer(0)
+    );
+    assert_eq!(
+        eval("(def-struct q (items (make-sorted-map))) (set a (q)) (set c (q)) (sorted-map-set (get-member a items) 1 2) (length (get-member c items))").unwrap(),
+        Value::Integer(0)
+    );
+
+    assert!(eval("(def-struct point (x 0 1))").is_err());
+    assert!(eval("(def-struct point (x 0) (x 1))").is_err());
+    assert!(eval("(def-struct point (x undefined))").is_err());
+}
+
+#[test]
+fn structure_named_construction() {
+    assert_eq!(
+        eval("(def-struct point (x 0) (y 0)) (set p (point x 1 y 2)) (list (get-member p x) (get-member p y))").unwrap(),
+        Value::List(vec![Value::Integer(1), Value::Integer(2)])
+    );
+    assert_eq!(
+        eval("(def-struct point (x 0) (y 0)) (set p (point y (* 2 3))) (list (get-member p x) (get-member p y))").unwrap(),
+        Value::List(vec![Value::Integer(0), Value::Integer(6)])
+    );
+    assert_eq!(
+        eval("(def-struct point x y) (= (point x 1 y 2) (point y 2 x 1))").unwrap(),
+        Value::Boolean(true)
+    );
+
+    // members without defaults must be given when any are
+    assert!(eval("(def-struct point x (y 0)) (point y 1)").is_err());
+    assert!(eval("(def-struct point x (y 0)) (point)").is_ok());
+
+    assert!(eval("(def-struct point (x 0)) (point z 1)").is_err());
+    assert!(eval("(def-struct point (x 0)) (point x 1 x 2)").is_err());
+    assert!(eval("(def-struct point (x 0)) (point x)").is_err());
+    assert!(eval("(def-struct point (x 0)) (point 1 2)").is_err());
+    assert!(eval("(def-struct point (x 0)) (point (x) 2)").is_err());
+}
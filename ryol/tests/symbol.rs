@@ -0,0 +1,78 @@
+use ryol::prelude::*;
+use ryol::symbol::get_interned_count;
+
+fn keyword(name: &str) -> Value {
+    Value::Keyword(Symbol::new(name))
+}
+
+#[test]
+fn symbol_interning_tests() {
+    let a = Symbol::new("a");
+    assert_eq!(a, Symbol::new("a"));
+    assert_eq!(a, Symbol::new(String::from("a").as_str()));
+    assert_ne!(a, Symbol::new("b"));
+    assert_eq!(a.get_name(), "a");
+}
+
+#[test]
+fn symbol_freeing_tests() {
+    let before = get_interned_count();
+    {
+        let symbol = Symbol::new("a name only used here");
+        let copy = symbol.clone();
+        assert_eq!(get_interned_count(), before + 1);
+        drop(symbol);
+        assert_eq!(copy, Symbol::new("a name only used here"));
+        assert_eq!(get_interned_count(), before + 1);
+    }
+    assert_eq!(get_interned_count(), before);
+
+    // names that are no longer used are freed, so gensym does not leak
+    eval("(times 1000 (gensym)) (times 1000 (symbol (format \"s\" (gensym))))").unwrap();
+    assert_eq!(get_interned_count(), before);
+}
+
+#[test]
+fn keyword_tests() {
+    assert_eq!(eval(":a").unwrap(), keyword("a"));
+    assert_eq!(
+        eval("(list :a :b)").unwrap(),
+        Value::List(vec![keyword("a"), keyword("b")])
+    );
+    assert_eq!(eval("(= :a :a)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(= :a :b)").unwrap(), Value::Boolean(false));
+    assert_ne!(eval(":a").unwrap(), eval("(quote a)").unwrap());
+    assert_eq!(eval("(quote :a)").unwrap(), keyword("a"));
+    assert!(eval("(:a 1)").is_err());
+
+    assert_eq!(format!("{}", eval(":a").unwrap()), ":a");
+    assert_eq!(format!("{:?}", eval(":a").unwrap()), "Value::Keyword(:a)");
+}
+
+#[test]
+fn keyword_usage_tests() {
+    assert_eq!(eval("(map-get {:a 1 :b 2} :b)").unwrap(), Value::Integer(2));
+    assert_eq!(
+        eval("(match :green (:red 0) (:green 1) (_ 2))").unwrap(),
+        Value::Integer(1)
+    );
+    assert_eq!(
+        eval("(defmacro tag (x) :tag) (tag 1)").unwrap(),
+        keyword("tag")
+    );
+}
+
+#[test]
+fn symbol_function_tests() {
+    assert_eq!(eval("(symbol \"a\")").unwrap(), eval("(quote a)").unwrap());
+    assert_eq!(eval("(keyword \"a\")").unwrap(), keyword("a"));
+    assert_eq!(eval("(keyword (quote a))").unwrap(), keyword("a"));
+    assert!(eval("(symbol \"\")").is_err());
+    assert!(eval("(symbol 1)").is_err());
+
+    assert_eq!(eval("(symbol? (quote a))").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(symbol? :a)").unwrap(), Value::Boolean(false));
+    assert_eq!(eval("(keyword? :a)").unwrap(), Value::Boolean(true));
+    assert_eq!(eval("(name :a)").unwrap(), Value::String("a".to_string()));
+    assert!(eval("(name \"a\")").is_err());
+}
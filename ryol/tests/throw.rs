@@ -5,3 +5,124 @@ fn throw_tests() {
     assert!(eval("(throw)").is_err());
     // todo: eventually test against format function
 }
+
+#[test]
+fn try_catch_tests() {
+    assert_eq!(eval("(try)").unwrap(), Value::Null);
+    assert_eq!(eval("(try 1 2)").unwrap(), Value::Integer(2));
+    assert!(eval("(try (throw \"error\"))").is_err());
+
+    assert_eq!(
+        eval("(try (throw \"error\") (catch e 5))").unwrap(),
+        Value::Integer(5)
+    );
+    assert_eq!(eval("(try 1 (catch e 5))").unwrap(), Value::Integer(1));
+    assert_eq!(
+        eval("(try (throw \"bad \" 1) (catch e (error-message e)))").unwrap(),
+        Value::String("bad 1".to_string())
+    );
+
+    // the error identifier only exists inside of the catch clause, so e is
+    // the constant again afterwards
+    assert_eq!(
+        eval("(try (throw \"error\") (catch e 5)) e").unwrap(),
+        Value::Float(std::f64::consts::E)
+    );
+
+    // errors from inside of a catch clause are not caught again
+    assert!(eval("(try (throw \"a\") (catch e (throw \"b\")))").is_err());
+
+    // caught errors can be rethrown
+    assert_eq!(
+        eval("(try (try (throw \"a\") (catch e (throw e))) (catch e (error-message e)))").unwrap(),
+        Value::String("a".to_string())
+    );
+}
+
+#[test]
+fn error_value_tests() {
+    assert_eq!(
+        eval("(try (throw \"a\") (catch e (error? e)))").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(eval("(error? 1)").unwrap(), Value::Boolean(false));
+    assert!(eval("(error-message 1)").is_err());
+
+    assert_eq!(
+        eval("(try (throw \"a\") (catch e (error-kind e)))").unwrap(),
+        Value::String("thrown".to_string())
+    );
+    assert_eq!(
+        eval("(try undefined (catch e (error-kind e)))").unwrap(),
+        Value::String("runtime".to_string())
+    );
+
+    let source = r#"(try
+    (throw "a")
+    (catch e (list (error-line e) (error-col e))))"#;
+    assert_eq!(
+        eval(source).unwrap(),
+        Value::List(vec![Value::Integer(2), Value::Integer(6)])
+    );
+}
+
+#[test]
+fn native_function_error_tests() {
+    assert_eq!(
+        eval("(try (+ 1 \"a\") (catch e (error-kind e)))").unwrap(),
+        Value::String("runtime".to_string())
+    );
+    assert_eq!(
+        eval("(try (+ 1 \"a\") (catch e (error-line e)))").unwrap(),
+        Value::Integer(1)
+    );
+
+    // errors from inside of functions are caught too
+    assert_eq!(
+        eval("(defn f (x) (+ x \"a\")) (try (f 1) (catch e \"caught\"))").unwrap(),
+        Value::String("caught".to_string())
+    );
+}
+
+#[test]
+fn finally_tests() {
+    assert_eq!(
+        eval("(set x 0) (try 1 (finally (set x 1))) x").unwrap(),
+        Value::Integer(1)
+    );
+    assert_eq!(
+        eval("(set x 0) (try (throw \"a\") (catch e ()) (finally (set x 1))) x").unwrap(),
+        Value::Integer(1)
+    );
+
+    // finally runs even when the error is not caught
+    let mut run_state = RunState::new();
+    assert!(run_state
+        .eval("(set x 0) (try (throw \"a\") (finally (set x 1)))")
+        .is_err());
+    assert_eq!(run_state.eval("x").unwrap(), Value::Integer(1));
+
+    // the result is from the body or catch and not the finally clause
+    assert_eq!(eval("(try 1 (finally 2))").unwrap(), Value::Integer(1));
+    assert_eq!(
+        eval("(try (throw \"a\") (catch e 1) (finally 2))").unwrap(),
+        Value::Integer(1)
+    );
+}
+
+#[test]
+fn try_control_flow_tests() {
+    // break and continue are not caught as errors
+    assert_eq!(
+        eval("(loop (try (break 1) (catch e 2)))").unwrap(),
+        Value::Integer(1)
+    );
+    assert_eq!(
+        eval("(set x 0) (loop (try (break) (finally (set x 1)))) x").unwrap(),
+        Value::Integer(1)
+    );
+    assert_eq!(
+        eval("(loop (try (throw \"error\") (finally (break 2))))").unwrap(),
+        Value::Integer(2)
+    );
+}